@@ -135,6 +135,31 @@ fn bench_path_find(c: &mut Criterion) {
                 }
             })
         })
+        .bench_function("path_tree_frozen_find", |b| {
+            let mut tree: PathTree<usize> = PathTree::new();
+            for (i, r) in ROUTES_WITH_COLON.iter().enumerate() {
+                tree.insert(r, i);
+            }
+            let tree = tree.freeze();
+            b.iter(|| {
+                for (i, r) in ROUTES_URLS.iter().enumerate() {
+                    let n = tree.find(r).unwrap();
+                    assert_eq!(*n.0, i);
+                }
+            })
+        })
+        .bench_function("path_tree_find_bytes", |b| {
+            let mut tree: PathTree<usize> = PathTree::new();
+            for (i, r) in ROUTES_WITH_COLON.iter().enumerate() {
+                tree.insert(r, i);
+            }
+            b.iter(|| {
+                for (i, r) in ROUTES_URLS.iter().enumerate() {
+                    let n = tree.find_bytes(r.as_bytes()).unwrap();
+                    assert_eq!(*n.0, i);
+                }
+            })
+        })
         .bench_function("route_recognizer_recognize", |b| {
             let mut router = RRRouter::<usize>::new();
             for (i, r) in ROUTES_WITH_COLON.iter().enumerate() {
@@ -0,0 +1,37 @@
+use std::{env, fs, path::Path};
+
+use path_tree::codegen::Generator;
+
+#[allow(dead_code)]
+#[path = "../tests/fixtures/github.rs"]
+mod github;
+
+fn main() {
+    let out = env::var_os("OUT_DIR").unwrap();
+
+    let mut generator = Generator::new("GithubRoute");
+    for (i, r) in github::ROUTES_WITH_COLON.iter().enumerate() {
+        generator.route(&format!("R{}", i), r);
+    }
+    let src = generator.generate().unwrap();
+    fs::write(Path::new(&out).join("github_routes.rs"), src).unwrap();
+
+    let src = Generator::new("Route")
+        .parse(include_str!("routes.txt"))
+        .unwrap()
+        .generate()
+        .unwrap();
+    fs::write(Path::new(&out).join("routes.rs"), src).unwrap();
+
+    let src = Generator::new("Item")
+        .with_separator("::")
+        .parse(include_str!("items.txt"))
+        .unwrap()
+        .generate()
+        .unwrap();
+    fs::write(Path::new(&out).join("items.rs"), src).unwrap();
+
+    println!("cargo:rerun-if-changed=routes.txt");
+    println!("cargo:rerun-if-changed=items.txt");
+    println!("cargo:rerun-if-changed=../tests/fixtures/github.rs");
+}
@@ -0,0 +1,2 @@
+//! Tests of the route enums generated by [`path_tree::codegen`] in a build
+//! script, see `build.rs` and `tests`.
@@ -0,0 +1,47 @@
+use path_tree::PathTree;
+
+#[allow(dead_code)]
+#[path = "../../tests/fixtures/github.rs"]
+mod github;
+
+use github::*;
+
+include!(concat!(env!("OUT_DIR"), "/github_routes.rs"));
+
+#[test]
+fn same_as_tree() {
+    let tree = ROUTES_WITH_COLON
+        .iter()
+        .enumerate()
+        .map(|(i, r)| (*r, i))
+        .collect::<PathTree<_>>();
+
+    let paths = ROUTES_URLS.iter().chain(&ROUTES_WITH_COLON).chain(&[
+        "/",
+        "",
+        "/repos",
+        "/repos/",
+        "/nope/nope",
+        "/gists/1/",
+    ]);
+
+    for path in paths {
+        let expected = tree.find(path).map(|(i, params)| {
+            let fields = params
+                .iter()
+                .map(|(k, v)| format!("{}: {:?}", k, v))
+                .collect::<Vec<_>>();
+            if fields.is_empty() {
+                format!("R{}", i)
+            } else {
+                format!("R{} {{ {} }}", i, fields.join(", "))
+            }
+        });
+        let found = GithubRoute::find(path);
+
+        assert_eq!(found.map(|r| format!("{:?}", r)), expected, "{}", path);
+        if let (Some(route), Some((i, _))) = (found, tree.find(path)) {
+            assert_eq!(route.pattern(), ROUTES_WITH_COLON[*i]);
+        }
+    }
+}
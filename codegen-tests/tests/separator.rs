@@ -0,0 +1,25 @@
+include!(concat!(env!("OUT_DIR"), "/items.rs"));
+
+#[test]
+fn many_char_separator() {
+    assert_eq!(Item::find("std"), Some(Item::Std));
+    assert_eq!(Item::find("::std"), Some(Item::Std));
+    assert_eq!(Item::find("std::fmt"), Some(Item::Module { module: "fmt" }));
+    assert_eq!(
+        Item::find("std::collections::hash_map::HashMap"),
+        Some(Item::Item {
+            module: "collections",
+            item: "hash_map::HashMap"
+        })
+    );
+    assert_eq!(Item::find("std::vec::Vec"), Some(Item::Vec));
+    assert_eq!(
+        Item::find("std::vec::Vec::new"),
+        Some(Item::Method {
+            module: "vec",
+            item: "Vec"
+        })
+    );
+    assert_eq!(Item::find("std:fmt"), None);
+    assert_eq!(Item::find("core::mem"), None);
+}
@@ -0,0 +1,96 @@
+use path_tree::PathTree;
+
+include!(concat!(env!("OUT_DIR"), "/routes.rs"));
+
+#[test]
+fn typed() {
+    assert_eq!(Route::find("/"), Some(Route::Index));
+    assert_eq!(Route::find("/users/42"), Some(Route::User { id: 42 }));
+    assert_eq!(Route::find("/users/fundon"), None);
+    assert_eq!(
+        Route::find("/users/7/repos/path-tree"),
+        Some(Route::Repo {
+            user_id: 7,
+            name: "path-tree"
+        })
+    );
+    assert_eq!(Route::find("/users/fundon/repos/path-tree"), None);
+    assert_eq!(
+        Route::find("/scores/0.5"),
+        Some(Route::Score { score: 0.5 })
+    );
+    assert_eq!(
+        Route::find("/files/a/b.rs"),
+        Some(Route::Files { path: "a/b.rs" })
+    );
+    assert_eq!(Route::find("/users/42").unwrap().pattern(), "/users/:id");
+}
+
+#[test]
+fn fall_through() {
+    assert_eq!(Route::find("/pages/2"), Some(Route::Page { n: 2 }));
+    assert_eq!(Route::find("/pages/2/edit"), Some(Route::Edit { n: 2 }));
+    // `n` is not a `u8`, the catch-all sibling is tried.
+    assert_eq!(Route::find("/pages/x"), Some(Route::Pages { rest: "x" }));
+    assert_eq!(
+        Route::find("/pages/256/edit"),
+        Some(Route::Pages { rest: "256/edit" })
+    );
+}
+
+/// Returns `true` if the value parses as the type of the route list.
+fn parses(ty: &str, value: &str) -> bool {
+    match ty {
+        "u8" => value.parse::<u8>().is_ok(),
+        "u64" => value.parse::<u64>().is_ok(),
+        "f32" => value.parse::<f32>().is_ok(),
+        _ => unreachable!("{}", ty),
+    }
+}
+
+#[test]
+fn same_as_tree() {
+    let routes = include_str!("../routes.txt")
+        .lines()
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(|l| l.split_whitespace().skip(1).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let mut tree = PathTree::new();
+    for (i, route) in routes.iter().enumerate() {
+        tree.insert(route[0], i);
+    }
+
+    let paths = [
+        "/",
+        "/users/42",
+        "/users/fundon",
+        "/users/7/repos/path-tree",
+        "/users/x/repos/path-tree",
+        "/scores/0.5",
+        "/scores/high",
+        "/files/a/b.rs",
+        "/pages/2",
+        "/pages/x",
+        "/pages/2/edit",
+        "/pages/x/edit",
+        "/pages/2/x",
+        "/pages/",
+    ];
+
+    for path in paths {
+        // The first route whose typed params all parse.
+        let expected = tree.find_all(path).find_map(|(i, params, _)| {
+            let route = &routes[*i];
+            route[1..]
+                .iter()
+                .map(|t| t.split_once('=').unwrap())
+                .all(|(name, ty)| {
+                    let (_, value) = params.iter().find(|(k, _)| *k == name).unwrap();
+                    parses(ty, value)
+                })
+                .then_some(route[0])
+        });
+
+        assert_eq!(Route::find(path).map(|r| r.pattern()), expected, "{}", path);
+    }
+}
@@ -1,35 +1,24 @@
-use std::{convert::Infallible, future::Future, pin::Pin, sync::Arc};
+use std::convert::Infallible;
 
 use hyper::{
     server::Server,
     service::{make_service_fn, service_fn},
-    Body, Request, Response, StatusCode,
+    Body, Method, Request, Response,
 };
-use path_tree::PathTree;
+use path_tree::{http::OwnedParams, tower::PathRouter};
+use tower::util::BoxCloneSyncService;
 
-static NOT_FOUND: &[u8] = b"Not Found";
+type Handler = BoxCloneSyncService<Request<Body>, Response<Body>, Infallible>;
 
-type Params = Vec<(String, String)>;
-
-trait Handler: Send + Sync + 'static {
-    fn call<'a>(
-        &'a self,
-        req: Request<Body>,
-    ) -> Pin<Box<dyn Future<Output = Response<Body>> + Send + 'a>>;
-}
-
-impl<F, R> Handler for F
+fn handler<F, R>(f: F) -> Handler
 where
-    F: Send + Sync + 'static + Fn(Request<Body>) -> R,
-    R: Future<Output = Response<Body>> + Send + 'static,
+    F: Fn(Request<Body>) -> R + Clone + Send + Sync + 'static,
+    R: std::future::Future<Output = Response<Body>> + Send + 'static,
 {
-    fn call<'a>(
-        &'a self,
-        req: Request<Body>,
-    ) -> Pin<Box<dyn Future<Output = Response<Body>> + Send + 'a>> {
-        let fut = (self)(req);
-        Box::pin(async move { fut.await })
-    }
+    BoxCloneSyncService::new(service_fn(move |req| {
+        let fut = f(req);
+        async move { Ok(fut.await) }
+    }))
 }
 
 async fn index(_: Request<Body>) -> Response<Body> {
@@ -37,20 +26,20 @@ async fn index(_: Request<Body>) -> Response<Body> {
 }
 
 async fn hello_world(req: Request<Body>) -> Response<Body> {
-    let params = req.extensions().get::<Params>().unwrap();
+    let params = req.extensions().get::<OwnedParams>().unwrap();
     let mut s = String::new();
     s.push_str("Hello, World!\n");
-    for (_, v) in params {
+    for (_, v) in params.iter() {
         s.push_str(&format!("param = {}", v));
     }
     Response::new(Body::from(s))
 }
 
 async fn hello_user(req: Request<Body>) -> Response<Body> {
-    let params = req.extensions().get::<Params>().unwrap();
+    let params = req.extensions().get::<OwnedParams>().unwrap();
     let mut s = String::new();
     s.push_str("Hello, ");
-    for (k, v) in params {
+    for (k, v) in params.iter() {
         s.push_str(&format!("{} = {}", k, v));
     }
     s.push('!');
@@ -69,41 +58,17 @@ async fn login(_req: Request<Body>) -> Response<Body> {
 async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let addr = ([127, 0, 0, 1], 3000).into();
 
-    let mut tree = PathTree::<Box<dyn Handler>>::new();
-    tree.insert("/GET/", Box::new(index));
-    tree.insert("/GET/*", Box::new(hello_world));
-    tree.insert("/GET/hello/:name", Box::new(hello_user));
-    tree.insert("/GET/rust", Box::new(hello_rust));
-    tree.insert("/POST/login", Box::new(login));
-
-    let tree = Arc::new(tree);
+    let mut router = PathRouter::new();
+    router
+        .route_method(Method::GET, "/", handler(index))
+        .route_method(Method::GET, "/*", handler(hello_world))
+        .route_method(Method::GET, "/hello/:name", handler(hello_user))
+        .route_method(Method::GET, "/rust", handler(hello_rust))
+        .route_method(Method::POST, "/login", handler(login));
 
     let make_service = make_service_fn(move |_| {
-        let router = Arc::clone(&tree);
-
-        async move {
-            Ok::<_, Infallible>(service_fn(move |mut req| {
-                let router = router.clone();
-                let path = "/".to_owned() + req.method().as_str() + req.uri().path();
-
-                async move {
-                    Ok::<_, Infallible>(match router.find(&path) {
-                        Some((handler, params)) => {
-                            let p = params
-                                .iter()
-                                .map(|p| (p.0.to_string(), p.1.to_string()))
-                                .collect::<Params>();
-                            req.extensions_mut().insert(p);
-                            handler.call(req).await
-                        }
-                        None => Response::builder()
-                            .status(StatusCode::NOT_FOUND)
-                            .body(NOT_FOUND.into())
-                            .unwrap(),
-                    })
-                }
-            }))
-        }
+        let router = router.clone();
+        async move { Ok::<_, Infallible>(router) }
     });
 
     let server = Server::bind(&addr).serve(make_service);
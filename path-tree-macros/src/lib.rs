@@ -0,0 +1,241 @@
+//! Compile-time route trees for [path-tree](https://docs.rs/path-tree).
+//!
+//! [`path_tree!`] defines a matching function from a list of routes,
+//! [`Routes`](derive@Routes) implements `path_tree::Routes` for an enum of
+//! routes.
+//!
+//! # Examples
+//!
+//! ```
+//! use path_tree_macros::path_tree;
+//!
+//! path_tree! {
+//!     /// Returns the page of a path.
+//!     pub fn page -> &'static str {
+//!         "/" => "index",
+//!         "/users/:id" => "user",
+//!         "/users/:user_id/repos/:id" => "repo",
+//!         "/*any" => "not found",
+//!     }
+//! }
+//!
+//! let (data, params) = page("/users/fundon/repos/path-tree").unwrap();
+//! assert_eq!(data, "repo");
+//! assert_eq!(&*params, [("user_id", "fundon"), ("id", "path-tree")]);
+//! ```
+
+#![deny(unsafe_code)]
+#![warn(
+    nonstandard_style,
+    rust_2018_idioms,
+    future_incompatible,
+    missing_debug_implementations
+)]
+
+mod routes;
+
+use path_tree::{codegen, PathTree};
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{
+    braced,
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+    punctuated::Punctuated,
+    Attribute, DeriveInput, Expr, Ident, LitStr, Token, Type, Visibility,
+};
+
+/// Defines a function matching paths against a fixed list of routes.
+///
+/// ```text
+/// path_tree! {
+///     pub fn name -> Type {
+///         "/pattern" => expression,
+///     }
+/// }
+/// ```
+///
+/// expands to a function returning the expression of the matched route and its
+/// params, with the same priorities as `PathTree::find`:
+///
+/// ```text
+/// pub fn name<'p>(path: &'p str) -> Option<(Type, path_tree::ArrayParams<'p, N>)>
+/// ```
+///
+/// where `N` is the most params of a route. The routes are checked like
+/// `PathTree::try_insert`, a conflict or an invalid pattern is a compile error.
+/// The matcher is generated as nested functions, nothing is built at runtime.
+///
+/// ```compile_fail
+/// path_tree_macros::path_tree! {
+///     fn page -> u8 {
+///         "/users/:id" => 0,
+///         "/users/:user_id" => 1,
+///     }
+/// }
+/// ```
+#[proc_macro]
+pub fn path_tree(input: TokenStream) -> TokenStream {
+    let routes = parse_macro_input!(input as Routes);
+
+    match routes.expand() {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// Implements `path_tree::Routes` for an enum of routes.
+///
+/// Every variant has a `#[route("...")]` attribute with its pattern and a
+/// field named after each of its params, the fields are parsed with
+/// [`FromStr`](std::str::FromStr) and displayed back into the path. When the
+/// params of a route can not be parsed, the next route matching the path in
+/// priority order is tried, like `PathTree::find_all` yields them. The routes
+/// are checked like `PathTree::try_insert` at compile time.
+///
+/// ```
+/// use path_tree::Routes;
+/// use path_tree_macros::Routes;
+///
+/// #[derive(Debug, PartialEq, Routes)]
+/// enum Route {
+///     #[route("/")]
+///     Index,
+///     #[route("/users/:id")]
+///     User { id: u64 },
+///     #[route("/users/:id/files/*path")]
+///     File { id: u64, path: String },
+/// }
+///
+/// let route = Route::recognize("/users/42/files/a/b.txt").unwrap();
+/// assert_eq!(
+///     route,
+///     Route::File {
+///         id: 42,
+///         path: "a/b.txt".to_owned()
+///     }
+/// );
+/// assert_eq!(route.to_path(), "/users/42/files/a/b.txt");
+/// assert_eq!(Route::recognize("/users/fundon"), None);
+/// ```
+///
+/// ```compile_fail
+/// #[derive(path_tree_macros::Routes)]
+/// enum Route {
+///     #[route("/users/:id")]
+///     User { user_id: u64 },
+/// }
+/// ```
+#[proc_macro_derive(Routes, attributes(route))]
+pub fn derive_routes(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match routes::expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+struct Routes {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    name: Ident,
+    ty: Type,
+    routes: Punctuated<Route, Token![,]>,
+}
+
+struct Route {
+    pattern: LitStr,
+    expr: Expr,
+}
+
+impl Parse for Routes {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        input.parse::<Token![fn]>()?;
+        let name = input.parse()?;
+        input.parse::<Token![->]>()?;
+        let ty = input.parse()?;
+
+        let content;
+        braced!(content in input);
+        let routes = content.parse_terminated(Route::parse, Token![,])?;
+
+        Ok(Self {
+            attrs,
+            vis,
+            name,
+            ty,
+            routes,
+        })
+    }
+}
+
+impl Parse for Route {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let pattern = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        let expr = input.parse()?;
+
+        Ok(Self { pattern, expr })
+    }
+}
+
+impl Routes {
+    fn expand(&self) -> syn::Result<proc_macro2::TokenStream> {
+        if self.routes.is_empty() {
+            return Err(syn::Error::new(self.name.span(), "no routes"));
+        }
+
+        let mut tree = PathTree::new();
+        let mut errors = None::<syn::Error>;
+
+        for (i, route) in self.routes.iter().enumerate() {
+            if let Err(e) = tree.try_insert(&route.pattern.value(), i) {
+                let e = syn::Error::new(route.pattern.span(), e);
+                match errors {
+                    Some(ref mut errors) => errors.combine(e),
+                    None => errors = Some(e),
+                }
+            }
+        }
+
+        if let Some(errors) = errors {
+            return Err(errors);
+        }
+
+        let matcher = codegen::matcher(&tree, "matcher")
+            .parse::<proc_macro2::TokenStream>()
+            .map_err(|e| syn::Error::new(self.name.span(), e))?;
+
+        let Self {
+            attrs,
+            vis,
+            name,
+            ty,
+            ..
+        } = self;
+        let n = tree.max_params();
+        let arms = self.routes.iter().enumerate().map(|(i, route)| {
+            let expr = &route.expr;
+            quote!(#i => #expr,)
+        });
+
+        Ok(quote! {
+            #(#attrs)*
+            #vis fn #name<'p>(
+                path: &'p str,
+            ) -> ::core::option::Option<(#ty, ::path_tree::ArrayParams<'p, #n>)> {
+                #matcher
+
+                let mut params = ::path_tree::ArrayParams::new();
+                let data = match matcher(path, &mut params)? {
+                    #(#arms)*
+                    _ => ::core::unreachable!(),
+                };
+                ::core::option::Option::Some((data, params))
+            }
+        })
+    }
+}
@@ -0,0 +1,217 @@
+use path_tree::{__private::params, codegen, PathTree};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{spanned::Spanned, Data, DeriveInput, Error, Fields, Ident, LitStr};
+
+pub(crate) fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let data = match input.data {
+        Data::Enum(ref data) => data,
+        _ => {
+            return Err(Error::new(
+                input.ident.span(),
+                "`Routes` can only be derived for enums",
+            ))
+        }
+    };
+    if !input.generics.params.is_empty() {
+        return Err(Error::new(
+            input.generics.span(),
+            "`Routes` can not be derived for generic enums",
+        ));
+    }
+    if data.variants.is_empty() {
+        return Err(Error::new(input.ident.span(), "no routes"));
+    }
+
+    let mut tree = PathTree::new();
+    let mut errors = Errors::default();
+    let mut arms = Vec::new();
+    let mut paths = Vec::new();
+
+    for (i, variant) in data.variants.iter().enumerate() {
+        let name = &variant.ident;
+        let pattern = match pattern(variant) {
+            Ok(pattern) => pattern,
+            Err(e) => {
+                errors.push(e);
+                continue;
+            }
+        };
+
+        if let Err(e) = tree.try_insert(&pattern.value(), i) {
+            errors.push(Error::new(pattern.span(), e));
+            continue;
+        }
+
+        let names = param_names(&pattern.value(), tree.separator());
+        if names.iter().any(String::is_empty) {
+            errors.push(Error::new(
+                pattern.span(),
+                "every param must be named to bind a field",
+            ));
+            continue;
+        }
+
+        let fields = match variant.fields {
+            Fields::Named(ref fields) => fields.named.iter().collect::<Vec<_>>(),
+            Fields::Unit => Vec::new(),
+            Fields::Unnamed(_) => {
+                errors.push(Error::new(
+                    variant.fields.span(),
+                    "tuple variants are not supported, name the fields after the params",
+                ));
+                continue;
+            }
+        };
+
+        // The field of each param, in the order of the params.
+        let mut bound = Vec::with_capacity(names.len());
+        for name in &names {
+            match fields
+                .iter()
+                .find(|f| unraw(f.ident.as_ref().unwrap()) == *name)
+            {
+                Some(field) => bound.push(*field),
+                None => errors.push(Error::new(
+                    variant.ident.span(),
+                    format!("no field for the param `{}`", name),
+                )),
+            }
+        }
+        for field in &fields {
+            let ident = field.ident.as_ref().unwrap();
+            if !names.contains(&unraw(ident)) {
+                errors.push(Error::new(
+                    ident.span(),
+                    format!("`{}` is not a param of `{}`", ident, pattern.value()),
+                ));
+            }
+        }
+        if bound.len() != names.len() {
+            continue;
+        }
+
+        let idents = bound
+            .iter()
+            .map(|f| f.ident.as_ref().unwrap())
+            .collect::<Vec<_>>();
+        let parsed = bound.iter().enumerate().map(|(n, field)| {
+            let ident = field.ident.as_ref().unwrap();
+            let ty = &field.ty;
+            quote!(#ident: <#ty as ::core::str::FromStr>::from_str(param(#n)).ok()?)
+        });
+        let format = format_string(&pattern.value(), tree.separator());
+
+        if matches!(variant.fields, Fields::Unit) {
+            arms.push(quote!(#i => Self::#name,));
+            paths.push(quote!(Self::#name => ::path_tree::__private::String::from(#format),));
+        } else {
+            arms.push(quote!(#i => Self::#name { #(#parsed,)* },));
+            paths.push(
+                quote!(Self::#name { #(#idents,)* } => ::path_tree::__private::format!(#format, #(#idents),*),),
+            );
+        }
+    }
+
+    errors.finish()?;
+
+    let name = &input.ident;
+    let n = tree.max_params();
+    let matcher = codegen::accepting_matcher(&tree, "matcher")
+        .parse::<TokenStream>()
+        .map_err(|e| Error::new(name.span(), e))?;
+    let param = if n > 0 {
+        quote!(let param = |i: usize| params.get(i).map_or("", |p| p.1);)
+    } else {
+        quote!(let _ = params;)
+    };
+
+    // A route with a param which can not be parsed is rejected by `parse`, the
+    // generated code goes on with the lower-priority routes.
+    Ok(quote! {
+        impl ::path_tree::Routes for #name {
+            fn recognize(path: &str) -> ::core::option::Option<Self> {
+                #matcher
+
+                let mut parse = |route: usize, params: &[(&str, &str)]| -> ::core::option::Option<Self> {
+                    #param
+                    ::core::option::Option::Some(match route {
+                        #(#arms)*
+                        _ => ::core::unreachable!(),
+                    })
+                };
+
+                let mut params = ::path_tree::ArrayParams::<#n>::new();
+                matcher(path, &mut params, &mut parse)
+            }
+
+            fn to_path(&self) -> ::path_tree::__private::String {
+                match self {
+                    #(#paths)*
+                }
+            }
+        }
+    })
+}
+
+/// Collects errors to report them at once.
+#[derive(Default)]
+struct Errors(Option<Error>);
+
+impl Errors {
+    fn push(&mut self, e: Error) {
+        match self.0 {
+            Some(ref mut errors) => errors.combine(e),
+            None => self.0 = Some(e),
+        }
+    }
+
+    fn finish(self) -> syn::Result<()> {
+        self.0.map_or(Ok(()), Err)
+    }
+}
+
+/// Returns the pattern of the `#[route("...")]` attribute of the variant.
+fn pattern(variant: &syn::Variant) -> syn::Result<LitStr> {
+    let mut attrs = variant.attrs.iter().filter(|a| a.path().is_ident("route"));
+
+    match (attrs.next(), attrs.next()) {
+        (Some(attr), None) => attr.parse_args(),
+        (Some(_), Some(attr)) => Err(Error::new(attr.span(), "a variant has a single route")),
+        (None, _) => Err(Error::new(
+            variant.ident.span(),
+            "missing `#[route(\"...\")]` attribute",
+        )),
+    }
+}
+
+/// Returns the param names of a pattern.
+fn param_names(pattern: &str, sep: &str) -> Vec<String> {
+    params(pattern, sep)
+        .map(|(_, range)| pattern[range].to_owned())
+        .collect()
+}
+
+/// Returns the format string of the path of a pattern, with a single leading
+/// separator and a `{}` for each param.
+fn format_string(pattern: &str, sep: &str) -> String {
+    let escape = |s: &str| s.replace('{', "{{").replace('}', "}}");
+    let pattern = pattern.trim_start_matches(sep);
+    let mut format = escape(sep);
+    let mut at = 0;
+
+    for (_, range) in params(pattern, sep) {
+        // Up to the `:` or `*` of the param.
+        format.push_str(&escape(&pattern[at..range.start - 1]));
+        format.push_str("{}");
+        at = range.end;
+    }
+    format.push_str(&escape(&pattern[at..]));
+
+    format
+}
+
+fn unraw(ident: &Ident) -> String {
+    let ident = ident.to_string();
+    ident.strip_prefix("r#").map(str::to_owned).unwrap_or(ident)
+}
@@ -0,0 +1,56 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+use path_tree::Routes;
+
+/// Counts the allocations of the current thread.
+struct Counting;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|n| n.set(n.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+fn allocations<F: FnOnce()>(f: F) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+#[derive(Debug, PartialEq, path_tree_macros::Routes)]
+enum Route {
+    #[route("/:id")]
+    Id { id: u64 },
+    #[route("/:id/:n")]
+    Page { id: u64, n: u8 },
+    #[route("/*rest")]
+    Rest { rest: char },
+}
+
+#[test]
+fn zero_allocations() {
+    let n = allocations(|| {
+        assert_eq!(Route::recognize("/7"), Some(Route::Id { id: 7 }));
+        assert_eq!(Route::recognize("/7/2"), Some(Route::Page { id: 7, n: 2 }));
+        // The params of the higher-priority routes can not be parsed.
+        assert_eq!(Route::recognize("/x"), Some(Route::Rest { rest: 'x' }));
+        assert_eq!(Route::recognize("/7/x"), None);
+        assert_eq!(Route::recognize("/xy"), None);
+    });
+    assert_eq!(n, 0);
+}
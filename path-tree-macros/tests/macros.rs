@@ -0,0 +1,94 @@
+use path_tree::PathTree;
+use path_tree_macros::path_tree;
+
+const ROUTES: [&str; 13] = [
+    "/",
+    "/users/:id",
+    "/users/:user_id/*",
+    "/users/:user_id/repos/:id",
+    "/users/new",
+    "/*any",
+    "/src/",
+    "/src/*filepath",
+    "/:name*ext",
+    "/files/:name",
+    "/café",
+    "/cafè/:id",
+    "/users/:id/:org",
+];
+
+path_tree! {
+    /// The routes of `ROUTES`, by index.
+    fn route -> usize {
+        "/" => 0,
+        "/users/:id" => 1,
+        "/users/:user_id/*" => 2,
+        "/users/:user_id/repos/:id" => 3,
+        "/users/new" => 4,
+        "/*any" => 5,
+        "/src/" => 6,
+        "/src/*filepath" => 7,
+        "/:name*ext" => 8,
+        "/files/:name" => 9,
+        "/café" => 10,
+        "/cafè/:id" => 11,
+        "/users/:id/:org" => 12,
+    }
+}
+
+path_tree! {
+    pub(crate) fn orders -> String {
+        "/orders/:id" => format!("order"),
+    }
+}
+
+#[test]
+fn same_as_tree() {
+    let tree = ROUTES
+        .iter()
+        .enumerate()
+        .map(|(i, r)| (*r, i))
+        .collect::<PathTree<_>>();
+
+    let paths = [
+        "/",
+        "",
+        "/users/42",
+        "/users/new",
+        "/users/news",
+        "/users/42/settings",
+        "/users/42/repos/7",
+        "/users/42/repos/7/branches",
+        "/users/42/viz-rs",
+        "/users/",
+        "/src",
+        "/src/",
+        "/src/lib.rs",
+        "/files/",
+        "/files/a/b",
+        "/about",
+        "/café",
+        "/cafè",
+        "/cafè/1",
+        "/cafê",
+        "users",
+        "users/42",
+        "src/lib.rs",
+    ];
+
+    for path in paths {
+        let expected = tree
+            .find(path)
+            .map(|(data, params)| (*data, params.to_vec()));
+        let found = route(path).map(|(data, params)| (data, params.to_vec()));
+        assert_eq!(found, expected, "{}", path);
+    }
+}
+
+#[test]
+fn expressions() {
+    let (data, params) = orders("/orders/42").unwrap();
+    assert_eq!(data, "order");
+    assert_eq!(&*params, [("id", "42")]);
+    assert!(orders("/orders").is_none());
+}
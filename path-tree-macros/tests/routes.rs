@@ -0,0 +1,118 @@
+use path_tree::Routes;
+
+#[derive(Clone, Debug, PartialEq, path_tree_macros::Routes)]
+enum Route {
+    #[route("/")]
+    Index,
+    #[route("/users/new")]
+    NewUser,
+    #[route("/users/:id")]
+    User { id: u64 },
+    #[route("/users/:id/repos/:repo")]
+    Repo { repo: String, id: u64 },
+    #[route("types/:type")]
+    Type { r#type: char },
+    #[route("/{braces}/*rest")]
+    Braces { rest: String },
+    #[route("/*any")]
+    Any { any: String },
+}
+
+#[test]
+fn recognize() {
+    assert_eq!(Route::recognize("/"), Some(Route::Index));
+    assert_eq!(Route::recognize("/users/new"), Some(Route::NewUser));
+    assert_eq!(Route::recognize("/users/42"), Some(Route::User { id: 42 }));
+    assert_eq!(
+        Route::recognize("/users/42/repos/path-tree"),
+        Some(Route::Repo {
+            id: 42,
+            repo: "path-tree".to_owned()
+        })
+    );
+    assert_eq!(
+        Route::recognize("/types/a"),
+        Some(Route::Type { r#type: 'a' })
+    );
+    assert_eq!(
+        Route::recognize("/{braces}/x/y"),
+        Some(Route::Braces {
+            rest: "x/y".to_owned()
+        })
+    );
+    assert_eq!(
+        Route::recognize("/about"),
+        Some(Route::Any {
+            any: "about".to_owned()
+        })
+    );
+}
+
+#[test]
+fn unparsable_params() {
+    // The lower-priority catch-all takes the paths the routes can not parse.
+    assert_eq!(
+        Route::recognize("/users/fundon"),
+        Some(Route::Any {
+            any: "users/fundon".to_owned()
+        })
+    );
+    assert_eq!(
+        Route::recognize("/types/ab"),
+        Some(Route::Any {
+            any: "types/ab".to_owned()
+        })
+    );
+
+    #[derive(Debug, PartialEq, path_tree_macros::Routes)]
+    enum Typed {
+        #[route("/items/:id")]
+        Id { id: u64 },
+        #[route("/items/:id/:n")]
+        Page { id: u64, n: u8 },
+        #[route("/items/*rest")]
+        Rest { rest: u64 },
+    }
+
+    assert_eq!(Typed::recognize("/items/7"), Some(Typed::Id { id: 7 }));
+    assert_eq!(
+        Typed::recognize("/items/7/2"),
+        Some(Typed::Page { id: 7, n: 2 })
+    );
+    // No route parses its params.
+    assert_eq!(Typed::recognize("/items/x"), None);
+    assert_eq!(Typed::recognize("/items/7/x"), None);
+}
+
+#[test]
+fn to_path() {
+    let routes = [
+        Route::Index,
+        Route::NewUser,
+        Route::User { id: 7 },
+        Route::Repo {
+            id: 7,
+            repo: "path-tree".to_owned(),
+        },
+        Route::Type { r#type: 'x' },
+        Route::Braces {
+            rest: "a/b".to_owned(),
+        },
+        Route::Any {
+            any: "about/us".to_owned(),
+        },
+    ];
+
+    for route in routes {
+        assert_eq!(Route::recognize(&route.to_path()), Some(route.clone()));
+    }
+
+    assert_eq!(Route::Type { r#type: 'x' }.to_path(), "/types/x");
+    assert_eq!(
+        Route::Braces {
+            rest: "a".to_owned()
+        }
+        .to_path(),
+        "/{braces}/a"
+    );
+}
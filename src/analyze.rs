@@ -0,0 +1,318 @@
+use alloc::{string::String, vec, vec::Vec};
+use core::{fmt, iter};
+
+use crate::{pattern, rename, Error, Node, NodeKind, PathTree, Walk};
+
+/// A problem found in a route set by [`analyze`] or [`PathTree::analyze`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Diagnostic {
+    /// The route is rejected by [`PathTree::try_insert`].
+    Invalid(Error),
+
+    /// The route only differs from an existing one by its param names,
+    /// the later one replaces the existing one.
+    Duplicate {
+        /// The pattern of the route.
+        route: String,
+        /// The pattern of the existing route.
+        existing: String,
+    },
+
+    /// No path matches the route.
+    Unreachable {
+        /// The pattern of the route.
+        route: String,
+    },
+
+    /// The paths of the route are matched by a higher-priority route.
+    Shadowed {
+        /// The pattern of the route.
+        route: String,
+        /// The pattern of the route that wins.
+        by: String,
+    },
+
+    /// Both routes match the same paths, the named parameter wins because
+    /// it is tried before the catch-all parameter.
+    Overlap {
+        /// The pattern of the route with a named parameter.
+        param: String,
+        /// The pattern of the route with a catch-all parameter.
+        catch_all: String,
+    },
+
+    /// The routes only differ by a trailing separator, like `/users/` and
+    /// `/users`, and one of them is never returned for its paths.
+    TrailingSlash {
+        /// The pattern of the route ending with the separator.
+        route: String,
+        /// The pattern of the route without it.
+        variant: String,
+    },
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid(e) => e.fmt(f),
+            Self::Duplicate { route, existing } => {
+                write!(f, "`{}` duplicates `{}`", route, existing)
+            }
+            Self::Unreachable { route } => write!(f, "`{}` never matches", route),
+            Self::Shadowed { route, by } => write!(f, "`{}` is shadowed by `{}`", route, by),
+            Self::Overlap { param, catch_all } => {
+                write!(f, "`{}` overlaps `{}` and wins", param, catch_all)
+            }
+            Self::TrailingSlash { route, variant } => {
+                write!(
+                    f,
+                    "`{}` only differs from `{}` by a trailing separator and one of them never matches",
+                    route, variant
+                )
+            }
+        }
+    }
+}
+
+/// The values tried for params in the witness paths, the first one no static
+/// node starts with is used.
+const WITNESSES: &str = "xyzwvutsrqponmlkjihgfedcba0123456789";
+
+/// Analyzes a route set for conflicts and shadowing, the segments are
+/// separated by `/`.
+///
+/// Unlike [`PathTree::analyze`], the routes replaced by a duplicate are
+/// reported too.
+///
+/// ```
+/// use path_tree::{analyze, Diagnostic};
+///
+/// let diagnostics = analyze(["/users/:id", "/users/:user_id", "/users/*any"]);
+///
+/// assert_eq!(
+///     diagnostics,
+///     [
+///         Diagnostic::Duplicate {
+///             route: "/users/:user_id".to_owned(),
+///             existing: "/users/:id".to_owned(),
+///         },
+///         Diagnostic::Overlap {
+///             param: "/users/:id".to_owned(),
+///             catch_all: "/users/*any".to_owned(),
+///         },
+///     ]
+/// );
+/// ```
+pub fn analyze<I>(routes: I) -> Vec<Diagnostic>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    analyze_with_separator("/", routes)
+}
+
+/// Like [`analyze`], the segments are separated by `sep`.
+///
+/// ```
+/// use path_tree::{analyze_with_separator, Diagnostic};
+///
+/// let diagnostics = analyze_with_separator(".", ["orders.:id", "orders.:id", "orders.*"]);
+///
+/// assert_eq!(
+///     diagnostics,
+///     [
+///         Diagnostic::Duplicate {
+///             route: "orders.:id".to_owned(),
+///             existing: ".orders.:id".to_owned(),
+///         },
+///         Diagnostic::Overlap {
+///             param: ".orders.:id".to_owned(),
+///             catch_all: ".orders.*".to_owned(),
+///         },
+///     ]
+/// );
+/// ```
+pub fn analyze_with_separator<I>(sep: &str, routes: I) -> Vec<Diagnostic>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let mut tree = PathTree::with_separator(sep);
+    let mut diagnostics = Vec::new();
+
+    for route in routes {
+        diagnostics.push(match tree.try_insert(route.as_ref(), ()) {
+            Ok(_) => continue,
+            Err(Error::Conflict { path, existing }) => Diagnostic::Duplicate {
+                route: path,
+                existing,
+            },
+            Err(e) => Diagnostic::Invalid(e),
+        });
+    }
+
+    diagnostics.extend(tree.analyze());
+    diagnostics
+}
+
+impl<T> PathTree<T> {
+    /// Analyzes the routes of the tree for shadowing and overlaps.
+    ///
+    /// A route is checked by looking up paths built from its pattern, with
+    /// params that no static segment starts with. A catch-all parameter is
+    /// also tried empty and deeper than the named parameters reach, the route
+    /// is only shadowed if none of these paths reach it first.
+    ///
+    /// ```
+    /// use path_tree::{Diagnostic, PathTree};
+    ///
+    /// let mut tree = PathTree::new();
+    /// tree.insert("/files/:name", 0);
+    /// tree.insert("/files/:name*ext", 1);
+    ///
+    /// assert_eq!(
+    ///     tree.analyze(),
+    ///     [Diagnostic::Unreachable {
+    ///         route: "/files/:name*ext".to_owned(),
+    ///     }]
+    /// );
+    /// ```
+    pub fn analyze(&self) -> Vec<Diagnostic> {
+        let sep = &*self.separator;
+        let mut diagnostics = Vec::new();
+
+        // The patterns without their param names, to find the variants.
+        let unnamed = self
+            .iter()
+            .map(|(route, _)| (rename(&route, &[], sep), route))
+            .collect::<Vec<_>>();
+
+        let value = self.witness_value();
+        let value = value.as_str();
+        let deep = [value].repeat(self.params + 1).join(sep);
+
+        let mut routes = self.iter();
+
+        while let Some(node) = routes.next_node() {
+            let trail = &routes.trail;
+            let route = pattern(trail);
+
+            // A catch-all is also tried empty and deeper than the named
+            // parameters reach.
+            let values = match node.kind {
+                NodeKind::CatchAll => vec!["", value, &deep],
+                _ => vec![value],
+            };
+
+            let mut by = None;
+            let mut reached = false;
+            let mut matched = false;
+
+            for value in values {
+                let path = witness(trail, value, value);
+                let mut walk = Walk::new(&self.root, &path, sep);
+
+                match walk.next_node() {
+                    Some(n) if core::ptr::eq(n, node) => {
+                        reached = true;
+                        break;
+                    }
+                    Some(_) => {
+                        let winner = pattern(&walk.trail);
+                        if iter::from_fn(|| walk.next_node()).any(|n| core::ptr::eq(n, node)) {
+                            matched = true;
+                            // The route winning the first path.
+                            by.get_or_insert(winner);
+                        }
+                    }
+                    None => {}
+                }
+            }
+
+            if !reached && !matched {
+                diagnostics.push(Diagnostic::Unreachable { route });
+                continue;
+            }
+
+            for (i, n) in trail.iter().enumerate().skip(1) {
+                if let NodeKind::Parameter = n.kind {
+                    if let Some(c) = trail[i - 1].catch_all().filter(|c| c.data.is_some()) {
+                        let mut catch_all = trail[..i].to_vec();
+                        catch_all.push(c);
+                        diagnostics.push(Diagnostic::Overlap {
+                            param: route.clone(),
+                            catch_all: pattern(&catch_all),
+                        });
+                    }
+                }
+            }
+
+            // A catch-all swallows the trailing separator. Both routes of a
+            // pair are fine when each one is returned for its paths.
+            if let Some(variant) = route
+                .strip_suffix(sep)
+                .filter(|_| !matches!(node.kind, NodeKind::CatchAll))
+                .map(|r| rename(r, &[], sep))
+                .and_then(|r| unnamed.iter().find(|(u, _)| *u == r))
+                .filter(|(_, variant)| {
+                    let path = witness(trail, value, value);
+                    let path = &path[..path.len() - sep.len()];
+                    let mut walk = Walk::new(&self.root, path, sep);
+                    !reached || walk.next_node().is_none() || pattern(&walk.trail) != **variant
+                })
+            {
+                diagnostics.push(Diagnostic::TrailingSlash {
+                    route: route.clone(),
+                    variant: variant.1.clone(),
+                });
+            }
+
+            if let (false, Some(by)) = (reached, by) {
+                diagnostics.push(Diagnostic::Shadowed { route, by });
+            }
+        }
+
+        diagnostics
+    }
+}
+
+impl<T> PathTree<T> {
+    /// Returns a value for the params of the witness paths, which no static
+    /// node starts with and without the separator.
+    fn witness_value(&self) -> String {
+        let mut starts = Vec::new();
+        first_chars(&self.root, &mut starts);
+
+        WITNESSES
+            .chars()
+            .find(|c| !starts.contains(c) && !self.separator.contains(*c))
+            .map_or_else(|| "\u{1}".into(), String::from)
+    }
+}
+
+/// Collects the first char of every static node.
+fn first_chars<T>(node: &Node<T>, chars: &mut Vec<char>) {
+    if let NodeKind::Static(ref s) = node.kind {
+        chars.extend(s.chars().next());
+    }
+    for child in node.nodes.iter().flatten() {
+        first_chars(child, chars);
+    }
+}
+
+/// Builds a path matching the nodes, named parameters get `value`, the
+/// catch-all parameter gets `rest`.
+fn witness<T>(trail: &[&Node<T>], value: &str, rest: &str) -> String {
+    let mut path = String::new();
+
+    for node in trail {
+        match node.kind {
+            NodeKind::Static(ref s) => path.push_str(s),
+            NodeKind::Parameter => path.push_str(value),
+            NodeKind::CatchAll => path.push_str(rest),
+        }
+    }
+
+    path
+}
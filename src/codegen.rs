@@ -0,0 +1,758 @@
+//! Rust source of a matcher specialized to a list of routes.
+//!
+//! A build script can turn a route list into a route enum with a field for
+//! each param and a `find` function, without building a tree at runtime and
+//! without a proc-macro. The generated matcher finds the same routes and
+//! params as [`PathTree::find`].
+//!
+//! A route list has a route per line, the name of its variant, its pattern
+//! and the types of its params as `name=Type`, the other params are `&str`.
+//! Blank lines and lines starting with `#` are skipped.
+//!
+//! # Examples
+//!
+//! In `build.rs`:
+//!
+//! ```no_run
+//! use path_tree::codegen::Generator;
+//!
+//! let routes = std::fs::read_to_string("routes.txt").unwrap();
+//! let src = Generator::new("Route")
+//!     .parse(&routes)
+//!     .unwrap()
+//!     .generate()
+//!     .unwrap();
+//!
+//! let out = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("routes.rs");
+//! std::fs::write(out, src).unwrap();
+//! ```
+//!
+//! With a `routes.txt` like:
+//!
+//! ```text
+//! # name  pattern                    types
+//! Index   /
+//! User    /users/:id                 id=u64
+//! Repo    /users/:user_id/repos/:id
+//! ```
+//!
+//! the generated source is included in the crate:
+//!
+//! ```ignore
+//! include!(concat!(env!("OUT_DIR"), "/routes.rs"));
+//!
+//! assert_eq!(
+//!     Route::find("/users/fundon/repos/path-tree"),
+//!     Some(Route::Repo { user_id: "fundon", id: "path-tree" })
+//! );
+//! assert_eq!(Route::find("/users/42"), Some(Route::User { id: 42 }));
+//! assert_eq!(Route::find("/users/fundon"), None);
+//! ```
+
+use alloc::{borrow::ToOwned, collections::BTreeSet, format, string::String, vec, vec::Vec};
+use core::{fmt, fmt::Write};
+
+use crate::{matcher, params, Error, Node, NodeKind, PathTree};
+
+/// Generates a route enum from a list of routes.
+#[derive(Clone, Debug)]
+pub struct Generator {
+    name: String,
+    separator: String,
+    routes: Vec<Route>,
+}
+
+#[derive(Clone, Debug)]
+struct Route {
+    variant: String,
+    pattern: String,
+    /// The types of the typed params, by name.
+    types: Vec<(String, String)>,
+    line: Option<usize>,
+}
+
+/// A route which can not be generated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodegenError {
+    /// The line of the route in the route list, starting at `1`.
+    pub line: Option<usize>,
+    /// What is wrong.
+    pub kind: ErrorKind,
+}
+
+/// The kind of a [`CodegenError`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The line is not a name followed by a pattern.
+    Parse(String),
+
+    /// The route is rejected by [`PathTree::try_insert`].
+    Route(Error),
+
+    /// The name of the variant is not an identifier or is used twice.
+    Variant(String),
+
+    /// The param name can not be a field name.
+    Param(String),
+
+    /// The typed param is not a param of the route, or is typed twice.
+    Type(String),
+}
+
+impl fmt::Display for CodegenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "line {}: ", line)?;
+        }
+
+        match self.kind {
+            ErrorKind::Parse(ref line) => {
+                write!(f, "expected a name and a pattern, found `{}`", line)
+            }
+            ErrorKind::Route(ref e) => e.fmt(f),
+            ErrorKind::Variant(ref name) => {
+                write!(f, "`{}` is not a valid or unique variant", name)
+            }
+            ErrorKind::Param(ref name) => write!(f, "param `{}` is not a valid field name", name),
+            ErrorKind::Type(ref name) => {
+                write!(
+                    f,
+                    "`{}` is not a param of the route or is typed twice",
+                    name
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CodegenError {}
+
+impl Generator {
+    /// Creates a generator of an enum with the name.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            separator: "/".to_owned(),
+            routes: Vec::new(),
+        }
+    }
+
+    /// Splits segments by `separator` instead of `/`, like
+    /// [`PathTree::with_separator`].
+    pub fn with_separator(mut self, separator: &str) -> Self {
+        self.separator = separator.to_owned();
+        self
+    }
+
+    /// Adds a route.
+    pub fn route(&mut self, variant: &str, pattern: &str) -> &mut Self {
+        self.typed_route(variant, pattern, &[])
+    }
+
+    /// Adds a route with the types of some of its params, by name.
+    pub fn typed_route(
+        &mut self,
+        variant: &str,
+        pattern: &str,
+        types: &[(&str, &str)],
+    ) -> &mut Self {
+        self.routes.push(Route {
+            variant: variant.to_owned(),
+            pattern: pattern.to_owned(),
+            types: types
+                .iter()
+                .map(|(name, ty)| ((*name).to_owned(), (*ty).to_owned()))
+                .collect(),
+            line: None,
+        });
+        self
+    }
+
+    /// Adds the routes of a route list, or returns every malformed line.
+    pub fn parse(&mut self, src: &str) -> Result<&mut Self, Vec<CodegenError>> {
+        let mut errors = Vec::new();
+
+        for (i, line) in src.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line.split_whitespace();
+            let types = fields
+                .clone()
+                .skip(2)
+                .map(|ty| ty.split_once('='))
+                .collect::<Option<Vec<_>>>();
+
+            match (fields.next(), fields.next(), types) {
+                (Some(variant), Some(pattern), Some(types)) => self.routes.push(Route {
+                    variant: variant.to_owned(),
+                    pattern: pattern.to_owned(),
+                    types: types
+                        .into_iter()
+                        .map(|(name, ty)| (name.to_owned(), ty.to_owned()))
+                        .collect(),
+                    line: Some(i + 1),
+                }),
+                _ => errors.push(CodegenError {
+                    line: Some(i + 1),
+                    kind: ErrorKind::Parse(line.to_owned()),
+                }),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(self)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Returns the source of the enum, or every route which can not be
+    /// generated.
+    ///
+    /// Each route becomes a variant with a field for each param, an unnamed
+    /// param is called `param1`, `param2` and so on. A typed param is parsed
+    /// with [`FromStr`](core::str::FromStr), the others are `&str`. The enum
+    /// has a `find` function returning the variant of a path, a route with a
+    /// typed param which can not be parsed is skipped like one which does not
+    /// match, and a `pattern` method. A catch-all parameter matched by its trailing separator alone,
+    /// like `/files/` for `/files/*path`, is empty.
+    ///
+    /// The enum derives `Clone`, `Debug` and `PartialEq`, and `Copy`, `Eq` and
+    /// `Hash` when no param is typed.
+    pub fn generate(&self) -> Result<String, Vec<CodegenError>> {
+        let mut tree = PathTree::with_separator(&self.separator);
+        let mut variants = BTreeSet::new();
+        let mut errors = Vec::new();
+        let mut fields = Vec::with_capacity(self.routes.len());
+
+        for (i, route) in self.routes.iter().enumerate() {
+            let mut error = |kind| {
+                errors.push(CodegenError {
+                    line: route.line,
+                    kind,
+                })
+            };
+
+            if !is_ident(&route.variant) || !variants.insert(route.variant.as_str()) {
+                error(ErrorKind::Variant(route.variant.clone()));
+            }
+
+            if let Err(e) = tree.try_insert(&route.pattern, i) {
+                error(ErrorKind::Route(e));
+                fields.push(Vec::new());
+                continue;
+            }
+
+            let names = params(&route.pattern, &self.separator)
+                .map(|(_, range)| &route.pattern[range])
+                .collect::<Vec<_>>();
+            for (j, (name, _)) in route.types.iter().enumerate() {
+                if !names.contains(&name.as_str())
+                    || route.types[..j].iter().any(|(n, _)| n == name)
+                {
+                    error(ErrorKind::Type(name.clone()));
+                }
+            }
+
+            let mut route_fields = Vec::with_capacity(names.len());
+            for (n, name) in names.iter().enumerate() {
+                let ty = route
+                    .types
+                    .iter()
+                    .find(|(n, _)| n == name)
+                    .map(|(_, ty)| ty.clone());
+                match field(name, n) {
+                    Some(field) => route_fields.push(Field {
+                        name: (*name).to_owned(),
+                        field,
+                        ty,
+                    }),
+                    None => error(ErrorKind::Param((*name).to_owned())),
+                }
+            }
+            fields.push(route_fields);
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(self.source(&tree, &fields))
+    }
+
+    fn source(&self, tree: &PathTree<usize>, fields: &[Vec<Field>]) -> String {
+        let borrowed = fields.iter().flatten().any(|f| f.ty.is_none());
+        let typed = fields.iter().flatten().any(|f| f.ty.is_some());
+        let lifetime = if borrowed { "<'p>" } else { "" };
+        let path = if borrowed { "&'p str" } else { "&str" };
+
+        let mut src = String::new();
+        src.push_str("// @generated by path_tree::codegen\n\n");
+        src.push_str("/// The routes of the route list.\n");
+        if typed {
+            src.push_str("#[derive(Clone, Debug, PartialEq)]\n");
+        } else {
+            src.push_str("#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]\n");
+        }
+        let _ = writeln!(src, "pub enum {}{} {{", self.name, lifetime);
+        for (route, fields) in self.routes.iter().zip(fields) {
+            let _ = writeln!(src, "    /// `{}`", route.pattern);
+            if fields.is_empty() {
+                let _ = writeln!(src, "    {},", route.variant);
+            } else {
+                let _ = writeln!(src, "    {} {{", route.variant);
+                for f in fields {
+                    let _ = writeln!(src, "        /// The `{}` param.", f.name);
+                    let ty = f.ty.as_deref().unwrap_or("&'p str");
+                    let _ = writeln!(src, "        {}: {},", f.field, ty);
+                }
+                src.push_str("    },\n");
+            }
+        }
+        src.push_str("}\n\n");
+
+        let _ = writeln!(src, "impl{} {}{} {{", lifetime, self.name, lifetime);
+        src.push_str("    /// Returns the route of the path.\n");
+        let _ = writeln!(src, "    pub fn find(path: {}) -> Option<Self> {{", path);
+        for line in accepting_matcher(tree, "matcher").lines() {
+            let _ = writeln!(src, "        {}", line);
+        }
+        src.push('\n');
+        let _ = writeln!(
+            src,
+            "        let mut params = ::path_tree::ArrayParams::<{}>::new();",
+            tree.max_params()
+        );
+        // A param which can not be parsed rejects the route, the lookup
+        // goes on with the next matching one.
+        src.push_str("        matcher(path, &mut params, &mut |route, params| {\n");
+        if !fields.iter().all(Vec::is_empty) {
+            src.push_str(
+                "            let param = |i: usize| params.get(i).map_or(\"\", |p| p.1);\n",
+            );
+        }
+        src.push_str("            Some(match route {\n");
+        for (i, (route, fields)) in self.routes.iter().zip(fields).enumerate() {
+            let _ = write!(src, "                {} => Self::{}", i, route.variant);
+            if !fields.is_empty() {
+                src.push_str(" {\n");
+                for (n, f) in fields.iter().enumerate() {
+                    match f.ty {
+                        Some(ref ty) => writeln!(
+                            src,
+                            "                    {}: <{} as ::core::str::FromStr>::from_str(param({})).ok()?,",
+                            f.field, ty, n
+                        ),
+                        None => writeln!(src, "                    {}: param({}),", f.field, n),
+                    }
+                    .ok();
+                }
+                src.push_str("                }");
+            }
+            src.push_str(",\n");
+        }
+        src.push_str("                _ => unreachable!(),\n");
+        src.push_str("            })\n");
+        src.push_str("        })\n");
+        src.push_str("    }\n\n");
+
+        src.push_str("    /// Returns the pattern of the route.\n");
+        src.push_str("    pub fn pattern(&self) -> &'static str {\n");
+        src.push_str("        match self {\n");
+        for (route, fields) in self.routes.iter().zip(fields) {
+            let rest = if fields.is_empty() { "" } else { " { .. }" };
+            let _ = writeln!(
+                src,
+                "            Self::{}{} => {:?},",
+                route.variant, rest, route.pattern
+            );
+        }
+        src.push_str("        }\n");
+        src.push_str("    }\n");
+        src.push_str("}\n");
+
+        src
+    }
+}
+
+/// The field of a param in its variant.
+struct Field {
+    name: String,
+    field: String,
+    ty: Option<String>,
+}
+
+/// Returns the field of the `n`th param, a raw identifier for a keyword.
+fn field(name: &str, n: usize) -> Option<String> {
+    if name.is_empty() {
+        Some(format!("param{}", n + 1))
+    } else if ["self", "Self", "super", "crate", "_"].contains(&name) {
+        None
+    } else if KEYWORDS.contains(&name) {
+        Some(format!("r#{}", name))
+    } else if is_ident(name) {
+        Some(name.to_owned())
+    } else {
+        None
+    }
+}
+
+fn is_ident(s: &str) -> bool {
+    let mut chars = s.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && s != "_"
+        && !KEYWORDS.contains(&s)
+}
+
+const KEYWORDS: [&str; 53] = [
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield", "_",
+];
+
+/// Returns the source of a function matching paths like [`PathTree::find_into`]:
+///
+/// ```text
+/// fn name<'p, P: ::path_tree::ParamsBuf<'p>>(path: &'p str, params: &mut P) -> Option<usize>
+/// ```
+///
+/// It returns the data of the matched route and writes its params into the
+/// buffer, every node of the tree becomes a nested function.
+pub fn matcher(tree: &PathTree<usize>, name: &str) -> String {
+    let mut src = String::new();
+    let _ = writeln!(
+        src,
+        "fn {}<'p, P: ::path_tree::ParamsBuf<'p>>(path: &'p str, params: &mut P) -> Option<usize> {{",
+        name
+    );
+    node_fns(&mut src, tree);
+    src.push_str("    params.truncate(0);\n");
+    src.push_str("    let found = n0(path, params, &mut |route, _| Some(route));\n");
+    finish(&mut src);
+
+    src
+}
+
+/// Returns the source of a function matching paths like [`matcher`], which
+/// passes the data and params of a matched route to `accept`:
+///
+/// ```text
+/// fn name<'p, P: ::path_tree::ParamsBuf<'p>, R>(
+///     path: &'p str,
+///     params: &mut P,
+///     accept: &mut impl FnMut(usize, &[(&'p str, &'p str)]) -> Option<R>,
+/// ) -> Option<R>
+/// ```
+///
+/// A route rejected by `accept` is skipped like a route which does not match,
+/// the lookup goes on with the next child of the nodes on the way to it. It
+/// returns the first value returned by `accept`.
+pub fn accepting_matcher(tree: &PathTree<usize>, name: &str) -> String {
+    let mut src = String::new();
+    let _ = writeln!(src, "fn {}<'p, P: ::path_tree::ParamsBuf<'p>, R>(", name);
+    src.push_str("    path: &'p str,\n");
+    src.push_str("    params: &mut P,\n");
+    src.push_str("    accept: &mut impl FnMut(usize, &[(&'p str, &'p str)]) -> Option<R>,\n");
+    src.push_str(") -> Option<R> {\n");
+    node_fns(&mut src, tree);
+    src.push_str("    params.truncate(0);\n");
+    src.push_str("    let found = n0(path, params, accept);\n");
+    finish(&mut src);
+
+    src
+}
+
+/// Writes a function for every node reached from the root.
+fn node_fns(src: &mut String, tree: &PathTree<usize>) {
+    let mut nodes = Vec::new();
+    number(&tree.root, &mut nodes);
+
+    // A parent is numbered before its children, it marks the ones it calls.
+    let mut reachable = vec![false; nodes.len()];
+    reachable[0] = true;
+    for (id, node) in nodes.iter().enumerate() {
+        if reachable[id] {
+            node_fn(src, id, node, &nodes, &mut reachable, &tree.separator);
+        }
+    }
+}
+
+fn finish(src: &mut String) {
+    src.push_str("    if found.is_none() {\n");
+    src.push_str("        params.truncate(0);\n");
+    src.push_str("    }\n");
+    src.push_str("    found\n");
+    src.push_str("}\n");
+}
+
+struct Numbered<'a> {
+    node: &'a Node<usize>,
+    /// The first byte and id of every child.
+    children: Vec<(u8, usize)>,
+}
+
+/// Numbers the nodes in depth-first order, the root is `0`.
+fn number<'a>(node: &'a Node<usize>, nodes: &mut Vec<Numbered<'a>>) -> usize {
+    let id = nodes.len();
+    nodes.push(Numbered {
+        node,
+        children: Vec::new(),
+    });
+
+    let indices = node.indices.as_deref().unwrap_or_default();
+    for (b, child) in indices.iter().zip(node.nodes.iter().flatten()) {
+        let child = number(child, nodes);
+        nodes[id].children.push((*b, child));
+    }
+
+    id
+}
+
+fn node_fn(
+    out: &mut String,
+    id: usize,
+    numbered: &Numbered<'_>,
+    nodes: &[Numbered<'_>],
+    reachable: &mut [bool],
+    sep: &str,
+) {
+    let node = numbered.node;
+    let child = |kind: fn(&NodeKind) -> bool, b: Option<u8>| {
+        numbered
+            .children
+            .iter()
+            .find(|(x, c)| kind(&nodes[*c].node.kind) && b.is_none_or(|b| *x == b))
+            .map(|(_, id)| *id)
+    };
+    let param = child(|k| *k == NodeKind::Parameter, None);
+    let catch_all = child(|k| *k == NodeKind::CatchAll, None);
+
+    let mut body = String::new();
+    let src = &mut body;
+    match node.kind {
+        NodeKind::Static(ref s) => {
+            if id == 0 && matcher::is_bare_root(s.as_bytes(), sep) {
+                // The leading separator of the root may be left out of the path.
+                let _ = writeln!(src, "        let p = p.strip_prefix({:?}).unwrap_or(p);", s);
+            } else {
+                let _ = writeln!(src, "        let p = p.strip_prefix({:?})?;", s);
+            }
+
+            src.push_str("        if p.is_empty() {\n");
+            match catch_all {
+                Some(c) if node.data.is_none() && s.ends_with(sep) => {
+                    reachable[c] = true;
+                    accept(src, nodes[c].node, "            ", false);
+                }
+                _ => accept(src, node, "            ", false),
+            }
+            src.push_str("        }\n");
+
+            let statics = numbered
+                .children
+                .iter()
+                .filter(|(_, c)| matches!(nodes[*c].node.kind, NodeKind::Static(_)))
+                .collect::<Vec<_>>();
+            for (_, c) in &statics {
+                reachable[*c] = true;
+            }
+            for c in param.iter().chain(&catch_all) {
+                reachable[*c] = true;
+            }
+
+            // Static, the chars of many children can start with the same byte.
+            let mut bytes = Vec::new();
+            for (b, _) in &statics {
+                if !bytes.contains(b) {
+                    bytes.push(*b);
+                }
+            }
+
+            // The last child tried is called in tail position, its caller
+            // drops the params it pushed if it fails.
+            let tail = param.is_none() && catch_all.is_none();
+            let shared = bytes.len() < statics.len();
+            if (!tail && !statics.is_empty())
+                || (param.is_some() && catch_all.is_some())
+                || (tail && shared)
+            {
+                src.push_str("        let len = params.len();\n");
+            }
+
+            if tail {
+                if bytes.is_empty() {
+                    src.push_str("        None\n");
+                } else {
+                    src.push_str("        match p.as_bytes()[0] {\n");
+                    for b in &bytes {
+                        let children = statics
+                            .iter()
+                            .filter(|(x, _)| x == b)
+                            .copied()
+                            .collect::<Vec<_>>();
+                        let (last, rest) = children.split_last().unwrap();
+                        if rest.is_empty() {
+                            let _ = writeln!(
+                                src,
+                                "            {} => n{}(p, params, accept),",
+                                byte(*b),
+                                last.1
+                            );
+                        } else {
+                            let _ = writeln!(src, "            {} => {{", byte(*b));
+                            try_children(src, rest, "                ");
+                            let _ = writeln!(src, "                n{}(p, params, accept)", last.1);
+                            src.push_str("            }\n");
+                        }
+                    }
+                    src.push_str("            _ => None,\n");
+                    src.push_str("        }\n");
+                }
+            } else {
+                match bytes[..] {
+                    [] => {}
+                    [b] => {
+                        let _ = writeln!(src, "        if p.as_bytes()[0] == {} {{", byte(b));
+                        try_children(src, &statics, "            ");
+                        src.push_str("        }\n");
+                    }
+                    _ => {
+                        src.push_str("        match p.as_bytes()[0] {\n");
+                        for b in &bytes {
+                            let children = statics
+                                .iter()
+                                .filter(|(x, _)| x == b)
+                                .copied()
+                                .collect::<Vec<_>>();
+                            let _ = writeln!(src, "            {} => {{", byte(*b));
+                            try_children(src, &children, "                ");
+                            src.push_str("            }\n");
+                        }
+                        src.push_str("            _ => {}\n");
+                        src.push_str("        }\n");
+                    }
+                }
+
+                // Named Parameter
+                match (param, catch_all) {
+                    (Some(p), Some(_)) => try_children(src, &[&(b':', p)], "        "),
+                    (Some(p), None) => {
+                        let _ = writeln!(src, "        n{}(p, params, accept)", p);
+                    }
+                    _ => {}
+                }
+
+                // Catch-All Parameter
+                if let Some(c) = catch_all {
+                    let _ = writeln!(src, "        n{}(p, params, accept)", c);
+                }
+            }
+        }
+        NodeKind::Parameter => {
+            match sep.parse::<char>() {
+                Ok(c) => writeln!(src, "        match p.find({:?}) {{", c),
+                Err(_) => writeln!(src, "        match p.find({:?}) {{", sep),
+            }
+            .ok();
+            src.push_str("            Some(i) => {\n");
+            src.push_str("                params.push((\"\", &p[..i]));\n");
+            match child(|k| matches!(k, NodeKind::Static(_)), sep.bytes().next()) {
+                Some(c) => {
+                    reachable[c] = true;
+                    let _ = writeln!(src, "                n{}(&p[i..], params, accept)", c);
+                }
+                None => src.push_str("                None\n"),
+            }
+            src.push_str("            }\n");
+            if node.data.is_some() {
+                src.push_str("            None => {\n");
+                src.push_str("                params.push((\"\", p));\n");
+                accept(src, node, "                ", true);
+                src.push_str("            }\n");
+            } else {
+                src.push_str("            None => None,\n");
+            }
+            src.push_str("        }\n");
+        }
+        NodeKind::CatchAll => {
+            src.push_str("        params.push((\"\", p));\n");
+            accept(src, node, "        ", true);
+        }
+    }
+
+    // A leaf without a route does not touch the params or call `accept`.
+    let _ = writeln!(
+        out,
+        "    fn n{}<'p, P: ::path_tree::ParamsBuf<'p>, R>(p: &'p str, {}: &mut P, {}: &mut impl FnMut(usize, &[(&'p str, &'p str)]) -> Option<R>) -> Option<R> {{",
+        id,
+        if body.contains("params") { "params" } else { "_" },
+        if body.contains("accept") { "accept" } else { "_" },
+    );
+    out.push_str(&body);
+    out.push_str("    }\n");
+}
+
+/// Passes the route of the node to `accept`, as the tail expression if `tail`.
+fn accept(src: &mut String, node: &Node<usize>, indent: &str, tail: bool) {
+    let data = match node.data {
+        Some(data) => data,
+        None if tail => {
+            let _ = writeln!(src, "{}None", indent);
+            return;
+        }
+        None => {
+            let _ = writeln!(src, "{}return None;", indent);
+            return;
+        }
+    };
+    match node.params {
+        Some(ref names) => {
+            let _ = writeln!(
+                src,
+                "{}for (name, (key, _)) in {:?}.into_iter().zip(params.as_mut_slice()) {{",
+                indent, names
+            );
+            let _ = writeln!(src, "{}    *key = name;", indent);
+            let _ = writeln!(src, "{}}}", indent);
+        }
+        None => {
+            let _ = writeln!(src, "{}params.truncate(0);", indent);
+        }
+    }
+    if tail {
+        let _ = writeln!(src, "{}accept({}, params.as_mut_slice())", indent, data);
+    } else {
+        let _ = writeln!(
+            src,
+            "{}return accept({}, params.as_mut_slice());",
+            indent, data
+        );
+    }
+}
+
+/// Tries the children in order, the params pushed by a failed one are dropped.
+fn try_children(src: &mut String, children: &[&(u8, usize)], indent: &str) {
+    for (_, c) in children {
+        let _ = writeln!(
+            src,
+            "{}if let Some(n) = n{}(p, params, accept) {{",
+            indent, c
+        );
+        let _ = writeln!(src, "{}    return Some(n);", indent);
+        let _ = writeln!(src, "{}}}", indent);
+        let _ = writeln!(src, "{}params.truncate(len);", indent);
+    }
+}
+
+/// Returns a byte literal.
+fn byte(b: u8) -> String {
+    format!("b'{}'", core::ascii::escape_default(b))
+}
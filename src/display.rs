@@ -0,0 +1,220 @@
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+use core::fmt::{self, Write};
+
+use crate::{pattern, Node, NodeKind, PathTree};
+
+/// Renders the node and its children as an indented tree.
+///
+/// Children are listed in the order a lookup tries them, a parameter node is
+/// labeled with the names the routes below it give the param, a node with
+/// data is followed by the pattern of its route and a node with scope data by
+/// `(scope)`.
+impl<T> fmt::Display for Node<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_node(f, self, &mut Vec::new())
+    }
+}
+
+/// Renders the tree from its root, see the [`Display`](fmt::Display) impl of [`Node`].
+///
+/// ```
+/// use path_tree::PathTree;
+///
+/// let mut tree = PathTree::new();
+/// tree.insert("/users/:id", 0).insert("/users", 1).insert("/*any", 2);
+///
+/// assert_eq!(
+///     tree.to_string(),
+///     "\
+/// /
+///   users [/users]
+///     /
+///       :id [/users/:id]
+///   *any [/*any]
+/// "
+/// );
+/// ```
+impl<T> fmt::Display for PathTree<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.root.fmt(f)
+    }
+}
+
+impl<T> PathTree<T> {
+    /// Renders the tree in the Graphviz DOT language.
+    ///
+    /// Children are ordered by priority, edges to named parameters are dashed
+    /// and edges to catch-all parameters are dotted. Parameter nodes are
+    /// labeled like in the [`Display`](fmt::Display) impl, nodes with data are
+    /// bold and also labeled with the pattern of their route.
+    ///
+    /// ```
+    /// use path_tree::PathTree;
+    ///
+    /// let mut tree = PathTree::new();
+    /// tree.insert("/:id", 0);
+    ///
+    /// assert_eq!(
+    ///     tree.to_dot(),
+    ///     "\
+    /// digraph {
+    ///     ordering=out;
+    ///     node [shape=box];
+    ///     n0 [label=\"/\"];
+    ///     n1 [label=\":id\\n/:id\", style=bold];
+    ///     n0 -> n1 [style=dashed];
+    /// }
+    /// "
+    /// );
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph {\n    ordering=out;\n    node [shape=box];\n");
+        let mut trail = Vec::new();
+        let mut id = 0;
+
+        write_dot(&mut dot, &self.root, &mut trail, &mut id);
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Returns the children of a node in the order a lookup tries them.
+fn children<T>(node: &Node<T>) -> Vec<&Node<T>> {
+    let mut nodes = node.nodes.iter().flatten().collect::<Vec<_>>();
+    nodes.sort_by_key(|n| match n.kind {
+        NodeKind::Static(_) => 0,
+        NodeKind::Parameter => 1,
+        NodeKind::CatchAll => 2,
+    });
+    nodes
+}
+
+/// Returns the label of the node, the segment of a static node or the param
+/// names of a parameter node, like `:owner` or `:owner|:org`.
+fn label<T>(node: &Node<T>, trail: &[&Node<T>]) -> String {
+    let prefix = match node.kind {
+        NodeKind::Static(ref s) => return s.clone(),
+        NodeKind::Parameter => ":",
+        NodeKind::CatchAll => "*",
+    };
+
+    // The param is named by the routes ending at or below the node.
+    let i = trail
+        .iter()
+        .filter(|n| !matches!(n.kind, NodeKind::Static(_)))
+        .count();
+    let mut names = Vec::new();
+    param_names(node, i, &mut names);
+
+    if names.is_empty() {
+        return prefix.to_owned();
+    }
+
+    let mut label = String::new();
+    for (j, name) in names.iter().enumerate() {
+        if j > 0 {
+            label.push('|');
+        }
+        label.push_str(prefix);
+        label.push_str(name);
+    }
+    label
+}
+
+/// Collects the distinct names of the `i`th param of the routes ending at or
+/// below the node, in priority order.
+fn param_names<'a, T>(node: &'a Node<T>, i: usize, names: &mut Vec<&'a str>) {
+    if let Some(name) = node.params.as_deref().and_then(|p| p.get(i)) {
+        if !names.contains(&name.as_str()) {
+            names.push(name);
+        }
+    }
+
+    for child in children(node) {
+        param_names(child, i, names);
+    }
+}
+
+fn write_node<'a, T>(
+    f: &mut fmt::Formatter<'_>,
+    node: &'a Node<T>,
+    trail: &mut Vec<&'a Node<T>>,
+) -> fmt::Result {
+    write!(f, "{:1$}{2}", "", trail.len() * 2, label(node, trail))?;
+    trail.push(node);
+
+    if node.data.is_some() {
+        write!(f, " [{}]", pattern(trail))?;
+    }
+    if node.scope.is_some() {
+        f.write_str(" (scope)")?;
+    }
+    f.write_char('\n')?;
+
+    for child in children(node) {
+        write_node(f, child, trail)?;
+    }
+
+    trail.pop();
+    Ok(())
+}
+
+/// Writes the node and its children, returns the id of the node.
+fn write_dot<'a, T>(
+    dot: &mut String,
+    node: &'a Node<T>,
+    trail: &mut Vec<&'a Node<T>>,
+    id: &mut usize,
+) -> usize {
+    let n = *id;
+    *id += 1;
+
+    let mut text = label(node, trail);
+    trail.push(node);
+
+    if node.data.is_some() {
+        text.push('\n');
+        text.push_str(&pattern(trail));
+    }
+    if node.scope.is_some() {
+        text.push_str("\n(scope)");
+    }
+
+    let _ = write!(dot, "    n{} [label=\"{}\"", n, escape(&text));
+    if node.data.is_some() {
+        dot.push_str(", style=bold");
+    }
+    dot.push_str("];\n");
+
+    for child in children(node) {
+        let c = write_dot(dot, child, trail, id);
+        let _ = write!(dot, "    n{} -> n{}", n, c);
+        dot.push_str(match child.kind {
+            NodeKind::Static(_) => ";\n",
+            NodeKind::Parameter => " [style=dashed];\n",
+            NodeKind::CatchAll => " [style=dotted];\n",
+        });
+    }
+
+    trail.pop();
+    n
+}
+
+/// Escapes a DOT string, newlines become centered line breaks.
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '"' | '\\' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push_str("\\n"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
@@ -0,0 +1,48 @@
+use alloc::string::String;
+use core::fmt;
+
+/// Errors of inserting routes into a tree.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The path is already taken by a route, possibly with other param names.
+    Conflict {
+        /// The pattern being inserted.
+        path: String,
+        /// The pattern of the existing route.
+        existing: String,
+    },
+
+    /// A param name is used more than once in the path.
+    DuplicateParam {
+        /// The pattern being inserted.
+        path: String,
+        /// The duplicated name.
+        name: String,
+    },
+
+    /// A catch-all parameter is followed by other segments.
+    CatchAllNotLast {
+        /// The pattern being inserted.
+        path: String,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Conflict { path, existing } => {
+                write!(f, "`{}` conflicts with `{}`", path, existing)
+            }
+            Self::DuplicateParam { path, name } => {
+                write!(f, "param `{}` is used more than once in `{}`", name, path)
+            }
+            Self::CatchAllNotLast { path } => {
+                write!(f, "catch-all parameter must be at the end of `{}`", path)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
@@ -0,0 +1,234 @@
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use crate::{link::Link, matcher, pattern, Node, NodeKind, PathTree};
+
+/// Why a node does not match the rest of the path.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Reason {
+    /// The path does not start with the static segment of the node.
+    Prefix,
+
+    /// The path ends at the node, which has no route.
+    NoRoute,
+
+    /// No child of the node matches the rest of the path.
+    NoChild,
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Prefix => "the path does not start with the static segment",
+            Self::NoRoute => "the path ends at a node without a route",
+            Self::NoChild => "no child matches the rest of the path",
+        })
+    }
+}
+
+/// A step of a lookup, see [`PathTree::explain`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Step<'a> {
+    /// A node is tried with the rest of the path.
+    Visit {
+        /// The depth of the node, the root is `0`.
+        depth: usize,
+        /// The kind of the node, which is the branch tried by its parent.
+        kind: NodeKind,
+        /// The rest of the path.
+        path: &'a str,
+    },
+
+    /// The previous branch failed, the params it captured are dropped and
+    /// the next branch is tried.
+    Backtrack {
+        /// The depth of the next branch.
+        depth: usize,
+    },
+
+    /// The node does not match.
+    Fail {
+        /// The depth of the node.
+        depth: usize,
+        /// Why the node does not match.
+        reason: Reason,
+    },
+
+    /// The node or one of its children matches.
+    Match {
+        /// The depth of the node.
+        depth: usize,
+    },
+}
+
+/// The steps of a lookup and the route it ends at.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Explanation<'a> {
+    /// The steps in the order they happened.
+    pub steps: Vec<Step<'a>>,
+    /// The pattern of the matched route.
+    pub route: Option<String>,
+}
+
+impl fmt::Display for Explanation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for step in &self.steps {
+            match step {
+                Step::Visit { depth, kind, path } => {
+                    write!(f, "{:1$}", "", depth * 2)?;
+                    match kind {
+                        NodeKind::Static(s) => write!(f, "static `{}`", s)?,
+                        NodeKind::Parameter => f.write_str("named parameter")?,
+                        NodeKind::CatchAll => f.write_str("catch-all parameter")?,
+                    }
+                    writeln!(f, " with `{}`", path)?;
+                }
+                Step::Backtrack { depth } => writeln!(f, "{:1$}backtrack", "", depth * 2)?,
+                Step::Fail { depth, reason } => {
+                    writeln!(f, "{:1$}failed: {2}", "", depth * 2, reason)?
+                }
+                Step::Match { depth } => writeln!(f, "{:1$}matched", "", depth * 2)?,
+            }
+        }
+
+        match self.route {
+            Some(ref route) => write!(f, "route `{}`", route),
+            None => f.write_str("no route"),
+        }
+    }
+}
+
+/// Hooks called by the lookup when it enters and leaves a node.
+pub(crate) trait Trace<'a, N, H: ?Sized = str> {
+    #[inline]
+    fn visit(&mut self, _node: N, _p: &'a H) {}
+
+    #[inline]
+    fn leave(&mut self, _found: Result<N, Reason>) {}
+}
+
+/// Traces nothing, the lookup compiles to the untraced one.
+impl<'a, N, H: ?Sized> Trace<'a, N, H> for () {}
+
+/// Records the steps of a lookup.
+struct Recorder<'a, T, K: Link> {
+    steps: Vec<Step<'a>>,
+    stack: Vec<&'a Node<T, K>>,
+    trail: Option<Vec<&'a Node<T, K>>>,
+}
+
+impl<'a, T, K: Link> Trace<'a, &'a Node<T, K>> for Recorder<'a, T, K> {
+    fn visit(&mut self, node: &'a Node<T, K>, p: &'a str) {
+        let depth = self.stack.len();
+
+        // A sibling failed before this node.
+        if matches!(self.steps.last(), Some(Step::Fail { depth: d, .. }) if *d == depth) {
+            self.steps.push(Step::Backtrack { depth });
+        }
+
+        self.steps.push(Step::Visit {
+            depth,
+            kind: node.kind.clone(),
+            path: p,
+        });
+        self.stack.push(node);
+    }
+
+    fn leave(&mut self, found: Result<&'a Node<T, K>, Reason>) {
+        let depth = self.stack.len() - 1;
+
+        match found {
+            Ok(n) => {
+                if self.trail.is_none() {
+                    let mut trail = self.stack.clone();
+                    // An exact static match may end at its catch-all child.
+                    if !core::ptr::eq(n, trail[depth]) {
+                        trail.push(n);
+                    }
+                    self.trail = Some(trail);
+                }
+                self.steps.push(Step::Match { depth });
+            }
+            Err(reason) => self.steps.push(Step::Fail { depth, reason }),
+        }
+
+        self.stack.pop();
+    }
+}
+
+/// Emits a `tracing` span for every node of a lookup.
+#[cfg(feature = "tracing")]
+#[derive(Default)]
+pub(crate) struct Spans(Vec<tracing::span::EnteredSpan>);
+
+#[cfg(feature = "tracing")]
+impl<'a, T, K: Link> Trace<'a, &'a Node<T, K>> for Spans {
+    fn visit(&mut self, node: &'a Node<T, K>, p: &'a str) {
+        self.0
+            .push(tracing::trace_span!("node", kind = ?node.kind, path = p).entered());
+    }
+
+    fn leave(&mut self, found: Result<&'a Node<T, K>, Reason>) {
+        match found {
+            Ok(_) => tracing::trace!("matched"),
+            Err(reason) => tracing::trace!(%reason, "failed"),
+        }
+        self.0.pop();
+    }
+}
+
+impl<T> PathTree<T> {
+    /// Looks up a path like [`PathTree::find`] and returns every node tried,
+    /// why it failed and where the lookup backtracked.
+    ///
+    /// The [`Display`](fmt::Display) output is an indented trace of the steps.
+    ///
+    /// ```
+    /// use path_tree::{NodeKind, PathTree, Reason, Step};
+    ///
+    /// let mut tree = PathTree::new();
+    /// tree.insert("/users/new", 0).insert("/users/:id", 1);
+    ///
+    /// let explanation = tree.explain("/users/news");
+    /// assert_eq!(explanation.route.as_deref(), Some("/users/:id"));
+    /// assert!(explanation.steps.contains(&Step::Fail {
+    ///     depth: 2,
+    ///     reason: Reason::NoChild,
+    /// }));
+    /// assert!(explanation.steps.contains(&Step::Visit {
+    ///     depth: 2,
+    ///     kind: NodeKind::Parameter,
+    ///     path: "news",
+    /// }));
+    /// ```
+    pub fn explain<'a>(&'a self, path: &'a str) -> Explanation<'a> {
+        explain(&self.root, path, &self.separator, self.params)
+    }
+}
+
+/// Looks up a path below the root and records the steps.
+pub(crate) fn explain<'a, T, K: Link>(
+    root: &'a Node<T, K>,
+    path: &'a str,
+    sep: &str,
+    capacity: usize,
+) -> Explanation<'a> {
+    let mut recorder = Recorder {
+        steps: Vec::new(),
+        stack: Vec::new(),
+        trail: None,
+    };
+
+    let mut params = Vec::with_capacity(capacity);
+    matcher::find(root, path, sep, &mut params, &mut recorder);
+
+    Explanation {
+        steps: recorder.steps,
+        route: recorder
+            .trail
+            .filter(|trail| trail.last().is_some_and(|n| n.data.is_some()))
+            .map(|trail| pattern(&trail)),
+    }
+}
@@ -0,0 +1,329 @@
+use crate::{
+    matcher::{self, Cursor, Kind, DISPATCH},
+    Haystack, NodeKind, ParamsBuf, PathTree,
+};
+use alloc::{boxed::Box, collections::VecDeque, string::String, vec, vec::Vec};
+
+/// No data, names or table.
+const NONE: u32 = u32::MAX;
+
+/// The longest static segment stored in its node.
+const INLINE: usize = 12;
+
+/// A read-only tree with its nodes in a contiguous arena.
+///
+/// The children of a node are stored next to each other, static ones first
+/// and sorted by their first byte, then the named parameter and the catch-all
+/// parameter. A node with many static children finds the first one starting
+/// with a byte in a table indexed by the byte. Short static segments are
+/// stored in their node, the longer ones share one string buffer.
+///
+/// This struct is created by [`PathTree::freeze`].
+#[derive(Clone, Debug)]
+pub struct FrozenPathTree<T> {
+    nodes: Vec<Frozen>,
+    /// The first byte of the segment of every node, indexed like `nodes`.
+    bytes: Vec<u8>,
+    /// The dispatch tables, the position after the first static child of a
+    /// node starting with every byte, `0` if none does.
+    tables: Vec<[u16; 256]>,
+    segments: String,
+    data: Vec<T>,
+    names: Vec<Box<[String]>>,
+    params: usize,
+    separator: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Type {
+    Static,
+    Parameter,
+    CatchAll,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Frozen {
+    kind: Type,
+    param: bool,
+    catch_all: bool,
+    /// The static segment if it is at most `INLINE` bytes long.
+    inline: [u8; INLINE],
+    /// The length of the static segment, and its start in `segments` if it
+    /// is not inline.
+    len: u32,
+    start: u32,
+    /// The first child and the number of static children.
+    children: u32,
+    statics: u32,
+    table: u32,
+    data: u32,
+    names: u32,
+}
+
+impl Frozen {
+    const EMPTY: Self = Self {
+        kind: Type::Static,
+        param: false,
+        catch_all: false,
+        inline: [0; INLINE],
+        len: 0,
+        start: 0,
+        children: 0,
+        statics: 0,
+        table: NONE,
+        data: NONE,
+        names: NONE,
+    };
+
+    #[inline]
+    fn param(&self) -> Option<usize> {
+        self.param.then(|| (self.children + self.statics) as usize)
+    }
+
+    #[inline]
+    fn catch_all(&self) -> Option<usize> {
+        self.catch_all
+            .then(|| (self.children + self.statics + self.param as u32) as usize)
+    }
+}
+
+/// A node of a frozen tree.
+#[derive(Debug)]
+struct At<'a, T> {
+    tree: &'a FrozenPathTree<T>,
+    id: usize,
+}
+
+impl<T> Clone for At<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for At<'_, T> {}
+
+impl<'a, T> At<'a, T> {
+    #[inline]
+    fn node(self) -> &'a Frozen {
+        &self.tree.nodes[self.id]
+    }
+
+    #[inline]
+    fn at(self, id: usize) -> Self {
+        Self { id, ..self }
+    }
+}
+
+impl<'a, T> Cursor<'a> for At<'a, T> {
+    #[inline]
+    fn kind(self) -> Kind<'a> {
+        let node = self.node();
+        match node.kind {
+            Type::Static if node.len as usize <= INLINE => {
+                Kind::Static(&node.inline[..node.len as usize])
+            }
+            Type::Static => Kind::Static(
+                &self.tree.segments.as_bytes()
+                    [node.start as usize..(node.start + node.len) as usize],
+            ),
+            Type::Parameter => Kind::Parameter,
+            Type::CatchAll => Kind::CatchAll,
+        }
+    }
+
+    #[inline]
+    fn has_data(self) -> bool {
+        self.node().data != NONE
+    }
+
+    #[inline]
+    fn names(self) -> Option<&'a [String]> {
+        self.tree
+            .names
+            .get(self.node().names as usize)
+            .map(|n| &**n)
+    }
+
+    #[inline]
+    fn statics(self, b: u8) -> impl Iterator<Item = Self> {
+        let node = self.node();
+        let bytes = &self.tree.bytes;
+        let start = node.children as usize;
+        let end = start + node.statics as usize;
+
+        let first = match self.tree.tables.get(node.table as usize) {
+            Some(table) => match table[b as usize] {
+                0 => end,
+                i => start + i as usize - 1,
+            },
+            None => (start..end).find(|i| bytes[*i] >= b).unwrap_or(end),
+        };
+
+        (first..end)
+            .take_while(move |i| bytes[*i] == b)
+            .map(move |id| self.at(id))
+    }
+
+    #[inline]
+    fn param(self) -> Option<Self> {
+        self.node().param().map(|id| self.at(id))
+    }
+
+    #[inline]
+    fn catch_all(self) -> Option<Self> {
+        self.node().catch_all().map(|id| self.at(id))
+    }
+}
+
+impl<T> PathTree<T> {
+    /// Converts the tree into a read-only tree which is faster to search.
+    ///
+    /// The data attached by scopes is dropped.
+    ///
+    /// ```
+    /// use path_tree::PathTree;
+    ///
+    /// let mut tree = PathTree::new();
+    /// tree.insert("/users/:id", 0).insert("/*any", 1);
+    ///
+    /// let tree = tree.freeze();
+    /// assert_eq!(tree.find("/users/42"), Some((&0, vec![("id", "42")])));
+    /// assert_eq!(tree.find("/about"), Some((&1, vec![("any", "about")])));
+    /// ```
+    pub fn freeze(self) -> FrozenPathTree<T> {
+        let mut frozen = FrozenPathTree {
+            nodes: vec![Frozen::EMPTY],
+            bytes: vec![0],
+            tables: Vec::new(),
+            segments: String::new(),
+            data: Vec::new(),
+            names: Vec::new(),
+            params: self.params,
+            separator: self.separator,
+        };
+        let mut queue = VecDeque::from([(self.root, 0)]);
+
+        // Breadth first, so the children of a node are allocated together.
+        while let Some((node, id)) = queue.pop_front() {
+            let mut slot = Frozen::EMPTY;
+
+            match node.kind {
+                NodeKind::Static(ref s) => {
+                    slot.len = s.len() as u32;
+                    if s.len() <= INLINE {
+                        slot.inline[..s.len()].copy_from_slice(s.as_bytes());
+                    } else {
+                        slot.start = frozen.segments.len() as u32;
+                        frozen.segments.push_str(s);
+                    }
+                    frozen.bytes[id] = s.as_bytes().first().copied().unwrap_or_default();
+                }
+                NodeKind::Parameter => slot.kind = Type::Parameter,
+                NodeKind::CatchAll => slot.kind = Type::CatchAll,
+            }
+
+            if let Some(data) = node.data {
+                slot.data = frozen.data.len() as u32;
+                frozen.data.push(data);
+            }
+            if let Some(names) = node.params {
+                slot.names = frozen.names.len() as u32;
+                frozen.names.push(names.into_boxed_slice());
+            }
+
+            // Stable, the children starting with the same byte keep their order.
+            let mut children = node.nodes.unwrap_or_default();
+            children.sort_by_key(|n| match n.kind {
+                NodeKind::Static(ref s) => (0, s.as_bytes()[0]),
+                NodeKind::Parameter => (1, 0),
+                NodeKind::CatchAll => (2, 0),
+            });
+
+            slot.children = frozen.nodes.len() as u32;
+            for child in children {
+                match child.kind {
+                    NodeKind::Static(_) => slot.statics += 1,
+                    NodeKind::Parameter => slot.param = true,
+                    NodeKind::CatchAll => slot.catch_all = true,
+                }
+                queue.push_back((child, frozen.nodes.len()));
+                frozen.nodes.push(Frozen::EMPTY);
+                frozen.bytes.push(0);
+            }
+
+            frozen.nodes[id] = slot;
+        }
+
+        // The first bytes are known once the children are visited.
+        for node in &mut frozen.nodes {
+            if (DISPATCH as u32..u16::MAX as u32).contains(&node.statics) {
+                let mut table = [0; 256];
+                let start = node.children as usize;
+                for i in (start..start + node.statics as usize).rev() {
+                    table[frozen.bytes[i] as usize] = (i - start + 1) as u16;
+                }
+                node.table = frozen.tables.len() as u32;
+                frozen.tables.push(table);
+            }
+        }
+
+        frozen
+    }
+}
+
+impl<T> FrozenPathTree<T> {
+    /// Returns the segment separator of the tree.
+    #[inline]
+    pub fn separator(&self) -> &str {
+        &self.separator
+    }
+
+    /// Returns a reference to the node data and params corresponding to the
+    /// path, like [`PathTree::find`].
+    pub fn find<'a>(&'a self, path: &'a str) -> Option<(&'a T, Vec<(&'a str, &'a str)>)> {
+        let mut params = Vec::with_capacity(self.params);
+        self.find_into(path, &mut params).map(|data| (data, params))
+    }
+
+    /// Returns a reference to the node data and params corresponding to the
+    /// path given as bytes, like [`PathTree::find_bytes`].
+    #[allow(clippy::type_complexity)]
+    pub fn find_bytes<'a>(&'a self, path: &'a [u8]) -> Option<(&'a T, Vec<(&'a str, &'a [u8])>)> {
+        let mut params = Vec::with_capacity(self.params);
+        self.find_with(path, &mut params).map(|data| (data, params))
+    }
+
+    /// Returns a reference to the node data corresponding to the path, the
+    /// params are written into the buffer, like [`PathTree::find_into`].
+    #[inline]
+    pub fn find_into<'a, P>(&'a self, path: &'a str, params: &mut P) -> Option<&'a T>
+    where
+        P: ParamsBuf<'a>,
+    {
+        self.find_with(path, params)
+    }
+
+    fn find_with<'a, H, P>(&'a self, path: &'a H, params: &mut P) -> Option<&'a T>
+    where
+        H: Haystack + ?Sized,
+        P: ParamsBuf<'a, H>,
+    {
+        params.truncate(0);
+
+        let root = At { tree: self, id: 0 };
+        let data = matcher::find(root, path, &self.separator, params, &mut ())
+            .and_then(|node| self.data.get(node.node().data as usize));
+
+        if data.is_none() {
+            params.truncate(0);
+        }
+        data
+    }
+
+    /// Returns the most params of a route, the capacity a params buffer needs.
+    #[inline]
+    pub fn max_params(&self) -> usize {
+        self.params
+    }
+}
@@ -0,0 +1,54 @@
+/// A path searched by the matcher, a `str` or bytes which may not be UTF-8.
+///
+/// The matcher only compares bytes, a cut is always at a char boundary since
+/// it follows a byte-exact static segment or precedes a separator.
+pub(crate) trait Haystack {
+    /// Returns the bytes of the path.
+    fn raw(&self) -> &[u8];
+
+    /// Splits the path at a byte offset.
+    fn cut(&self, i: usize) -> (&Self, &Self);
+
+    /// Returns the byte offset of the first separator.
+    fn find_sep(&self, sep: &str) -> Option<usize>;
+}
+
+impl Haystack for str {
+    #[inline]
+    fn raw(&self) -> &[u8] {
+        self.as_bytes()
+    }
+
+    #[inline]
+    fn cut(&self, i: usize) -> (&Self, &Self) {
+        self.split_at(i)
+    }
+
+    #[inline]
+    fn find_sep(&self, sep: &str) -> Option<usize> {
+        match *sep.as_bytes() {
+            [b] => self.find(char::from(b)),
+            _ => self.find(sep),
+        }
+    }
+}
+
+impl Haystack for [u8] {
+    #[inline]
+    fn raw(&self) -> &[u8] {
+        self
+    }
+
+    #[inline]
+    fn cut(&self, i: usize) -> (&Self, &Self) {
+        self.split_at(i)
+    }
+
+    #[inline]
+    fn find_sep(&self, sep: &str) -> Option<usize> {
+        match *sep.as_bytes() {
+            [b] => self.iter().position(|x| *x == b),
+            ref sep => self.windows(sep.len()).position(|w| w == sep),
+        }
+    }
+}
@@ -0,0 +1,258 @@
+//! Matching [`http`](::http) requests by their method, host and path.
+//!
+//! ```
+//! use http::{Method, Request};
+//! use path_tree::http::{OwnedParams, RequestTree};
+//!
+//! let mut tree = RequestTree::new();
+//! tree.insert(Some(Method::GET), "/users/:id", "user")
+//!     .insert(None, "/*any", "page")
+//!     .insert_host("api.example.com", None, "/users/:id", "api user");
+//!
+//! let mut req = Request::get("/users/42?tab=repos").body(()).unwrap();
+//! let (data, params) = tree.find_request(&req).unwrap();
+//! assert_eq!(data, &"user");
+//! assert_eq!(params.get("id"), Some("42"));
+//!
+//! req.extensions_mut().insert(params);
+//! assert_eq!(req.extensions().get::<OwnedParams>().unwrap().get("id"), Some("42"));
+//!
+//! let req = Request::delete("/users/42").body(()).unwrap();
+//! assert_eq!(tree.find_request(&req), None);
+//!
+//! let req = Request::delete("/repos").body(()).unwrap();
+//! assert_eq!(tree.find_request(&req).unwrap().0, &"page");
+//!
+//! let req = Request::delete("http://api.example.com:8080/users/42")
+//!     .body(())
+//!     .unwrap();
+//! assert_eq!(tree.find_request(&req).unwrap().0, &"api user");
+//! ```
+
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+use std::ops::Deref;
+
+use ::http::{header::HOST, Method, Request, Uri};
+
+use crate::PathTree;
+
+/// The params of a route owned, so they can be kept in the extensions of a
+/// request.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OwnedParams(Vec<(String, String)>);
+
+impl OwnedParams {
+    /// Returns the value of the first param with the name.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+impl Deref for OwnedParams {
+    type Target = [(String, String)];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'a> FromIterator<(&'a str, &'a str)> for OwnedParams {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        Self(
+            iter.into_iter()
+                .map(|(k, v)| (k.to_owned(), v.to_owned()))
+                .collect(),
+        )
+    }
+}
+
+/// A tree of routes for the requests of a method, or of any method, and of a
+/// host, or of any host.
+///
+/// The routes of the host of a request are searched before the routes of any
+/// host, the routes of its method before the routes of any method. The data
+/// found borrows the tree only, not the request, so the params can be moved
+/// into the request afterwards.
+#[derive(Clone, Debug)]
+pub struct RequestTree<T> {
+    any: PathTree<Endpoint>,
+    hosts: Vec<(String, PathTree<Endpoint>)>,
+    data: Vec<T>,
+}
+
+/// The indices of the data of a route, by method.
+#[derive(Clone, Debug, Default)]
+struct Endpoint {
+    any: Option<usize>,
+    methods: Vec<(Method, usize)>,
+}
+
+impl Endpoint {
+    fn get(&self, method: &Method) -> Option<usize> {
+        self.methods
+            .iter()
+            .find(|(m, _)| m == method)
+            .map(|(_, i)| *i)
+            .or(self.any)
+    }
+}
+
+/// The reason a request has no route, see [`RequestTree::lookup`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Miss {
+    NotFound,
+    /// The path has routes for these methods only.
+    MethodNotAllowed(Vec<Method>),
+}
+
+impl<T> Default for RequestTree<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> RequestTree<T> {
+    /// Creates a tree without routes.
+    pub fn new() -> Self {
+        Self {
+            any: PathTree::new(),
+            hosts: Vec::new(),
+            data: Vec::new(),
+        }
+    }
+
+    /// Inserts a route for the requests of the method, or of any method, to
+    /// any host.
+    ///
+    /// The data of an existing route with the same method and path is
+    /// replaced, like [`PathTree::insert`] does.
+    pub fn insert(&mut self, method: Option<Method>, path: &str, data: T) -> &mut Self {
+        let endpoint = self
+            .any
+            .insert_node(path)
+            .data
+            .get_or_insert_with(Endpoint::default);
+        Self::set(&mut self.data, endpoint, method, data);
+        self
+    }
+
+    /// Inserts a route for the requests of the method, or of any method, to
+    /// the host. The host is compared without its port and case.
+    pub fn insert_host(
+        &mut self,
+        host: &str,
+        method: Option<Method>,
+        path: &str,
+        data: T,
+    ) -> &mut Self {
+        let host = host.to_ascii_lowercase();
+        let i = match self.hosts.iter().position(|(h, _)| *h == host) {
+            Some(i) => i,
+            None => {
+                self.hosts.push((host, PathTree::new()));
+                self.hosts.len() - 1
+            }
+        };
+        let endpoint = self.hosts[i]
+            .1
+            .insert_node(path)
+            .data
+            .get_or_insert_with(Endpoint::default);
+        Self::set(&mut self.data, endpoint, method, data);
+        self
+    }
+
+    fn set(data: &mut Vec<T>, endpoint: &mut Endpoint, method: Option<Method>, value: T) {
+        let slot = match method {
+            Some(ref method) => endpoint
+                .methods
+                .iter()
+                .find(|(m, _)| m == method)
+                .map(|(_, i)| *i),
+            None => endpoint.any,
+        };
+
+        if let Some(i) = slot {
+            data[i] = value;
+            return;
+        }
+
+        data.push(value);
+        let i = data.len() - 1;
+        match method {
+            Some(method) => endpoint.methods.push((method, i)),
+            None => endpoint.any = Some(i),
+        }
+    }
+
+    /// Finds the route of a request by its method, the path of its URI and
+    /// its host, taken from the URI or else from the `Host` header.
+    ///
+    /// The query is ignored.
+    pub fn find_request<B>(&self, req: &Request<B>) -> Option<(&T, OwnedParams)> {
+        self.lookup(req).ok()
+    }
+
+    /// Finds the route of a method and URI, the host is taken from the URI.
+    pub fn find_uri(&self, method: &Method, uri: &Uri) -> Option<(&T, OwnedParams)> {
+        self.find(method, uri.host(), uri.path()).ok()
+    }
+
+    /// Like [`RequestTree::find_request`], telling a path without routes from
+    /// a path without a route for the method of the request.
+    pub(crate) fn lookup<B>(&self, req: &Request<B>) -> Result<(&T, OwnedParams), Miss> {
+        let host = req.uri().host().or_else(|| {
+            let host = req.headers().get(HOST)?.to_str().ok()?;
+            // The port is after the last colon, unless it ends an IPv6 address.
+            Some(match host.rfind(':') {
+                Some(i) if !host[i..].contains(']') => &host[..i],
+                _ => host,
+            })
+        });
+        self.find(req.method(), host, req.uri().path())
+    }
+
+    fn find(
+        &self,
+        method: &Method,
+        host: Option<&str>,
+        path: &str,
+    ) -> Result<(&T, OwnedParams), Miss> {
+        let host = host.and_then(|host| {
+            self.hosts
+                .iter()
+                .find(|(h, _)| h.eq_ignore_ascii_case(host))
+                .map(|(_, tree)| tree)
+        });
+
+        let mut allowed = Vec::new();
+        for tree in host.into_iter().chain([&self.any]) {
+            if let Some((endpoint, params)) = tree.find(path) {
+                match endpoint.get(method) {
+                    Some(i) => return Ok((&self.data[i], params.into_iter().collect())),
+                    None => {
+                        for (m, _) in &endpoint.methods {
+                            if !allowed.contains(m) {
+                                allowed.push(m.clone());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        if allowed.is_empty() {
+            Err(Miss::NotFound)
+        } else {
+            Err(Miss::MethodNotAllowed(allowed))
+        }
+    }
+}
@@ -38,6 +38,7 @@
 //! assert_eq!(r.1, vec![("user_id", "31415926"), ("", "repos/53589793/branches")]);
 //! ```
 
+#![no_std]
 #![deny(unsafe_code)]
 #![warn(
     nonstandard_style,
@@ -46,8 +47,71 @@
     missing_debug_implementations
 )]
 
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+mod analyze;
+pub mod codegen;
+mod display;
+mod error;
+mod explain;
+mod frozen;
+mod haystack;
+#[cfg(feature = "http")]
+pub mod http;
+mod link;
+#[cfg(any(feature = "toml", feature = "json", feature = "yaml"))]
+pub mod manifest;
+mod matcher;
+pub mod mqtt;
+#[cfg(feature = "openapi")]
+pub mod openapi;
+mod params;
+mod persistent;
+mod routes;
+mod scope;
+#[cfg(feature = "serde")]
+mod serialize;
+#[cfg(feature = "shared")]
+mod shared;
+#[cfg(feature = "tower")]
+pub mod tower;
+
+pub use analyze::{analyze, analyze_with_separator, Diagnostic};
+pub use error::Error;
+pub use explain::{Explanation, Reason, Step};
+pub use frozen::FrozenPathTree;
+pub use params::{ArrayParams, ParamsBuf};
+pub use persistent::PersistentPathTree;
+pub use routes::Routes;
+pub use scope::Scope;
+#[cfg(feature = "shared")]
+pub use shared::{SharedPathTree, Snapshot};
+
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::{format, string::String};
+
+    /// Returns the kind and the range of the name of every param of the
+    /// pattern, for `path-tree-macros`.
+    pub fn params<'a>(
+        pattern: &'a str,
+        sep: &'a str,
+    ) -> impl Iterator<Item = (u8, core::ops::Range<usize>)> + 'a {
+        crate::params(pattern, sep)
+    }
+}
+
+use alloc::{borrow::ToOwned, boxed::Box, string::String, vec, vec::Vec};
+
+use core::{fmt, iter, ops::Range};
+
+use haystack::Haystack;
+use link::{Link, Owned, Shared};
+
 /// The Kind of a node.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum NodeKind {
     /// A static node with a path
     Static(String),
@@ -60,50 +124,130 @@ pub enum NodeKind {
 }
 
 /// A node stores kind data params indices and children nodes.
-#[derive(Clone, Debug)]
-pub struct Node<T> {
+///
+/// The nodes of a [`PathTree`] own their children, the nodes of a
+/// [`PersistentPathTree`] share them with their copies.
+pub struct Node<T, K: Link = Owned> {
     kind: NodeKind,
-    data: Option<T>,
-    indices: Option<Vec<char>>,
-    nodes: Option<Vec<Self>>,
+    data: Option<K::Data<T>>,
+    scope: Option<K::Data<T>>,
+    /// The first byte of every child, `:` and `*` for the parameters.
+    indices: Option<Vec<u8>>,
+    /// The position after the child starting with every byte, `0` if none
+    /// does and [`MANY`] if several do, for a node with many children.
+    table: Option<Box<[u16; 256]>>,
+    nodes: Option<Vec<K::Child<T>>>,
     params: Option<Vec<String>>,
 }
 
-impl<T> Default for Node<T> {
+/// The entry of a byte starting several children, which are scanned.
+const MANY: u16 = u16::MAX;
+
+impl<T: Clone> Clone for Node<T> {
+    fn clone(&self) -> Self {
+        Self {
+            kind: self.kind.clone(),
+            data: self.data.clone(),
+            scope: self.scope.clone(),
+            indices: self.indices.clone(),
+            table: self.table.clone(),
+            nodes: self.nodes.clone(),
+            params: self.params.clone(),
+        }
+    }
+}
+
+/// A copy shares the children and the data of the node.
+impl<T> Clone for Node<T, Shared> {
+    fn clone(&self) -> Self {
+        Self {
+            kind: self.kind.clone(),
+            data: self.data.clone(),
+            scope: self.scope.clone(),
+            indices: self.indices.clone(),
+            table: self.table.clone(),
+            nodes: self.nodes.clone(),
+            params: self.params.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug, K: Link> fmt::Debug for Node<T, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Node")
+            .field("kind", &self.kind)
+            .field("data", &self.data.as_ref().map(K::data))
+            .field("scope", &self.scope.as_ref().map(K::data))
+            .field("indices", &self.indices)
+            .field(
+                "nodes",
+                &self
+                    .nodes
+                    .as_ref()
+                    .map(|nodes| nodes.iter().map(K::child).collect::<Vec<_>>()),
+            )
+            .field("params", &self.params)
+            .finish()
+    }
+}
+
+impl<T, K: Link> Default for Node<T, K> {
     #[inline]
     fn default() -> Self {
         Self::new(NodeKind::Static(String::new()))
     }
 }
 
-impl<T> Node<T> {
+impl<T, K: Link> Node<T, K> {
     /// Creates a new node with a special kind.
     #[inline]
     pub fn new(kind: NodeKind) -> Self {
         Self {
             kind,
             data: None,
+            scope: None,
             nodes: None,
             params: None,
             indices: None,
+            table: None,
         }
     }
 
+    /// Rebuilds the dispatch table after the children changed.
+    fn index(&mut self) {
+        let indices = self.indices.as_deref().unwrap_or_default();
+        self.table = (matcher::DISPATCH..MANY as usize)
+            .contains(&indices.len())
+            .then(|| {
+                let mut table = Box::new([0; 256]);
+                for (i, b) in indices.iter().enumerate() {
+                    let entry = &mut table[*b as usize];
+                    *entry = if *entry == 0 { i as u16 + 1 } else { MANY };
+                }
+                table
+            });
+    }
+
     fn add_node(&mut self, c: char, kind: NodeKind) -> &mut Self {
-        let indices: &mut Vec<char> = self.indices.get_or_insert_with(Vec::new);
-        let nodes: &mut Vec<Node<T>> = self.nodes.get_or_insert_with(Vec::new);
-
-        match position(indices, c) {
-            Some(i) => match kind {
-                NodeKind::Static(ref s) => nodes[i].insert(s),
-                _ => &mut nodes[i],
-            },
-            None => {
-                indices.push(c);
-                nodes.push(Node::new(kind));
-                nodes.last_mut().unwrap()
-            }
+        let indices: &mut Vec<u8> = self.indices.get_or_insert_with(Vec::new);
+        let nodes: &mut Vec<K::Child<T>> = self.nodes.get_or_insert_with(Vec::new);
+
+        let i = match kind {
+            NodeKind::Static(_) => position::<T, K>(indices, nodes, c),
+            ref kind => dynamic::<T, K>(nodes, kind),
+        };
+        if let Some(i) = i {
+            let node = K::child_mut(&mut self.nodes.as_mut().unwrap()[i]);
+            return match kind {
+                NodeKind::Static(ref s) => node.insert(s),
+                _ => node,
+            };
         }
+
+        indices.push(first_byte(c));
+        nodes.push(K::new_child(Node::new(kind)));
+        self.index();
+        K::child_mut(self.nodes.as_mut().unwrap().last_mut().unwrap())
     }
 
     /// Adds a child node witch a static path.
@@ -135,13 +279,15 @@ impl<T> Node<T> {
                     *s = s[l..].to_owned();
                     let mut node = Node {
                         data: None,
+                        scope: None,
                         params: None,
                         nodes: Some(Vec::new()),
-                        indices: s.chars().next().map(|c| vec![c]),
+                        indices: s.as_bytes().first().map(|b| vec![*b]),
+                        table: None,
                         kind: NodeKind::Static(String::from(&p[0..l])),
                     };
-                    ::std::mem::swap(self, &mut node);
-                    self.nodes.as_mut().unwrap().push(node);
+                    core::mem::swap(self, &mut node);
+                    self.nodes.as_mut().unwrap().push(K::new_child(node));
                 }
 
                 if l == p.len() {
@@ -155,6 +301,112 @@ impl<T> Node<T> {
         }
     }
 
+    /// Returns the node of the pattern below this root and the param names
+    /// of the pattern, creating the missing nodes on the way.
+    fn locate(&mut self, mut path: &str, sep: &str) -> (&mut Self, Option<Vec<String>>) {
+        let mut next = true;
+        let mut node = self;
+        let mut params: Option<Vec<String>> = None;
+
+        path = path.trim_start_matches(sep);
+
+        if path.is_empty() {
+            return (node, params);
+        }
+
+        while next {
+            match find_param(path, sep) {
+                Some(i) => {
+                    let kind: NodeKind;
+                    let (mut prefix, mut suffix) = path.split_at(i);
+
+                    if !prefix.is_empty() {
+                        node = node.add_node_static(prefix);
+                    }
+
+                    prefix = &suffix[..1];
+                    suffix = &suffix[1..];
+
+                    let c = prefix.chars().next().unwrap();
+                    if c == ':' {
+                        match param_end(suffix, sep) {
+                            Some(i) => {
+                                path = &suffix[i..];
+                                suffix = &suffix[..i];
+                            }
+                            None => {
+                                next = false;
+                            }
+                        }
+                        kind = NodeKind::Parameter;
+                    } else {
+                        next = false;
+                        kind = NodeKind::CatchAll;
+                    }
+                    params.get_or_insert_with(Vec::new).push(suffix.to_owned());
+                    node = node.add_node_dynamic(c, kind);
+                }
+                None => {
+                    next = false;
+                    node = node.add_node_static(path);
+                }
+            }
+        }
+
+        (node, params)
+    }
+
+    /// Removes the data of the route at the rest of the pattern `p`, below
+    /// this node, and drops the children left empty.
+    fn remove(&mut self, p: &str, sep: &str) -> Option<K::Data<T>> {
+        let c = match p.chars().next() {
+            Some(c) => c,
+            None => {
+                let data = self.data.take();
+                if self.scope.is_none() {
+                    self.params = None;
+                }
+                return data;
+            }
+        };
+
+        let nodes = self.nodes.as_mut()?;
+        let (i, rest) = match c {
+            // A `:` starting a separator is static.
+            ':' if !p.starts_with(sep) => {
+                let p = &p[1..];
+                (
+                    dynamic::<T, K>(nodes, &NodeKind::Parameter)?,
+                    &p[param_end(p, sep).unwrap_or(p.len())..],
+                )
+            }
+            '*' => (dynamic::<T, K>(nodes, &NodeKind::CatchAll)?, ""),
+            _ => {
+                let i = position::<T, K>(self.indices.as_deref()?, nodes, c)?;
+                match K::child(&nodes[i]).kind {
+                    NodeKind::Static(ref s) => (i, p.strip_prefix(s.as_str())?),
+                    _ => return None,
+                }
+            }
+        };
+
+        let child = K::child_mut(&mut nodes[i]);
+        let data = child.remove(rest, sep);
+
+        if child.data.is_none()
+            && child.scope.is_none()
+            && child.nodes.as_ref().is_none_or(Vec::is_empty)
+        {
+            nodes.remove(i);
+            if let Some(indices) = self.indices.as_mut() {
+                indices.remove(i);
+            }
+            self.index();
+        }
+
+        data
+    }
+
     /// Returns a reference to the node corresponding to the path.
     #[inline]
     pub fn find<'a>(&'a self, p: &'a str) -> Option<(&'a Self, Vec<(&'a str, &'a str)>)> {
@@ -168,111 +420,73 @@ impl<T> Node<T> {
         p: &'a str,
         capacity: usize,
     ) -> Option<(&'a Self, Vec<(&'a str, &'a str)>)> {
-        let mut params = Vec::with_capacity(capacity);
-
-        self.find_inner(p, &mut params).map(|node| {
-            (
-                node,
-                node.params.as_ref().map_or_else(Vec::new, |node_params| {
-                    for (value, (key, _)) in node_params.iter().zip(params.iter_mut()) {
-                        *key = value;
-                    }
-
-                    params
-                }),
-            )
-        })
+        self.find_with_separator(p, "/", capacity)
     }
 
-    fn find_inner<'a>(
+    /// Returns a reference to the node corresponding to the path,
+    /// using `sep` as the segment separator.
+    #[inline]
+    pub fn find_with_separator<'a>(
         &'a self,
-        mut p: &'a str,
-        params: &mut Vec<(&'a str, &'a str)>,
-    ) -> Option<&'a Self> {
-        match self.kind {
-            NodeKind::Static(ref s) => {
-                let l = loc(s, p);
-
-                if l == 0 || l < s.len() {
-                    None
-                } else if l == s.len() && l == p.len() {
-                    Some(
-                        // Fixed: has only route `/*`
-                        // Ended `/` `/*any`
-                        if self.data.is_none() && self.indices.is_some() && s.ends_with('/') {
-                            &self.nodes.as_ref().unwrap()
-                                [position(self.indices.as_ref().unwrap(), '*')?]
-                        } else {
-                            self
-                        },
-                    )
-                } else {
-                    let indices = self.indices.as_ref()?;
-                    let nodes = self.nodes.as_ref()?;
-
-                    p = &p[l..];
+        p: &'a str,
+        sep: &str,
+        capacity: usize,
+    ) -> Option<(&'a Self, Vec<(&'a str, &'a str)>)> {
+        let mut params = Vec::with_capacity(capacity);
+        self.find_into(p, sep, &mut params)
+            .map(|node| (node, params))
+    }
 
-                    // Static
-                    if let Some(i) = position(indices, p.chars().next().unwrap()) {
-                        if let Some(n) = nodes[i].find_inner(p, params).as_mut() {
-                            return Some(
-                                // Ended `/` `/*any`
-                                match &n.kind {
-                                    NodeKind::Static(s)
-                                        if n.data.is_none()
-                                            && n.indices.is_some()
-                                            && s.ends_with('/') =>
-                                    {
-                                        &n.nodes.as_ref().unwrap()
-                                            [position(n.indices.as_ref().unwrap(), '*')?]
-                                    }
-                                    _ => n,
-                                },
-                            );
-                        }
-                    }
+    /// Returns a reference to the node corresponding to the path, the params
+    /// are written into the buffer.
+    ///
+    /// The buffer is cleared first, and left empty if no node matches.
+    pub fn find_into<'a, P>(&'a self, p: &'a str, sep: &str, params: &mut P) -> Option<&'a Self>
+    where
+        P: ParamsBuf<'a>,
+    {
+        params.truncate(0);
 
-                    // Named Parameter
-                    if let Some(i) = position(indices, ':') {
-                        if let Some(n) = nodes[i].find_inner(p, params).as_mut() {
-                            return Some(n);
-                        }
-                    }
+        #[cfg(feature = "tracing")]
+        if tracing::enabled!(tracing::Level::TRACE) {
+            return matcher::find(self, p, sep, params, &mut explain::Spans::default());
+        }
 
-                    // Catch-All Parameter
-                    if let Some(i) = position(indices, '*') {
-                        if let Some(n) = nodes[i].find_inner(p, params).as_mut() {
-                            return Some(n);
-                        }
-                    }
+        matcher::find(self, p, sep, params, &mut ())
+    }
 
-                    None
-                }
-            }
-            NodeKind::Parameter => match p.find('/') {
-                Some(i) => {
-                    let indices = self.indices.as_ref()?;
+    /// Returns a reference to the node corresponding to the path given as
+    /// bytes, which do not need to be UTF-8.
+    #[allow(clippy::type_complexity)]
+    pub fn find_bytes<'a>(
+        &'a self,
+        p: &'a [u8],
+        sep: &str,
+        capacity: usize,
+    ) -> Option<(&'a Self, Vec<(&'a str, &'a [u8])>)> {
+        let mut params = Vec::with_capacity(capacity);
+        matcher::find(self, p, sep, &mut params, &mut ()).map(|node| (node, params))
+    }
 
-                    params.push(("", &p[..i]));
-                    p = &p[i..];
+    /// Returns the static child starting with the char.
+    #[inline]
+    fn child(&self, c: char) -> Option<&Self> {
+        let nodes = self.nodes.as_ref()?;
+        position::<T, K>(self.indices.as_ref()?, nodes, c).map(|i| K::child(&nodes[i]))
+    }
 
-                    let n = self.nodes.as_ref().unwrap()
-                        [position(indices, p.chars().next().unwrap())?]
-                    .find_inner(p, params)?;
+    /// Returns the named parameter child.
+    #[inline]
+    fn param(&self) -> Option<&Self> {
+        let nodes = self.nodes.as_ref()?;
+        dynamic::<T, K>(nodes, &NodeKind::Parameter).map(|i| K::child(&nodes[i]))
+    }
 
-                    Some(n)
-                }
-                None if self.params.is_some() => {
-                    params.push(("", p));
-                    Some(self)
-                }
-                None => None,
-            },
-            NodeKind::CatchAll => {
-                params.push(("", p));
-                Some(self)
-            }
-        }
+    /// Returns the catch-all parameter child.
+    #[inline]
+    fn catch_all(&self) -> Option<&Self> {
+        let nodes = self.nodes.as_ref()?;
+        dynamic::<T, K>(nodes, &NodeKind::CatchAll).map(|i| K::child(&nodes[i]))
     }
 }
 
@@ -281,6 +495,7 @@ impl<T> Node<T> {
 pub struct PathTree<T> {
     root: Node<T>,
     params: usize,
+    separator: String,
 }
 
 impl<T> Default for PathTree<T> {
@@ -296,85 +511,1003 @@ impl<T> PathTree<T> {
     /// The root node is a static node with `/`.
     #[inline]
     pub fn new() -> Self {
+        Self::with_separator("/")
+    }
+
+    /// Creates a new tree which splits segments by `separator` instead of `/`.
+    ///
+    /// The root node is a static node with the separator. Named parameters stop
+    /// at the separator, catch-all parameters span it. Like patterns, paths
+    /// may leave out the leading separator, unlike the paths of a `/` tree.
+    ///
+    /// The separator may have many chars, like `::`. In a pattern, a `:`
+    /// starting a separator belongs to it, the next one starts a param.
+    ///
+    /// ```
+    /// use path_tree::PathTree;
+    ///
+    /// let mut tree = PathTree::with_separator(".");
+    /// tree.insert("orders.:id.created", 0);
+    /// tree.insert("orders.*rest", 1);
+    ///
+    /// let r = tree.find("orders.42.created").unwrap();
+    /// assert_eq!(r.0, &0);
+    /// assert_eq!(r.1, vec![("id", "42")]);
+    ///
+    /// let r = tree.find(".orders.42.shipped").unwrap();
+    /// assert_eq!(r.0, &1);
+    /// assert_eq!(r.1, vec![("rest", "42.shipped")]);
+    ///
+    /// let mut tree = PathTree::with_separator("::");
+    /// tree.insert("std:::module::*item", 0);
+    ///
+    /// let r = tree.find("std::collections::hash_map::HashMap").unwrap();
+    /// assert_eq!(r.1, vec![("module", "collections"), ("item", "hash_map::HashMap")]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the separator is empty, is `:` or contains `*`.
+    #[inline]
+    pub fn with_separator(separator: &str) -> Self {
+        assert!(
+            is_separator(separator),
+            "`{}` can not be used as a separator",
+            separator
+        );
+
         Self {
-            root: Node::new(NodeKind::Static("/".to_owned())),
+            root: Node::new(NodeKind::Static(separator.to_owned())),
             params: 0,
+            separator: separator.to_owned(),
         }
     }
 
+    /// Returns the segment separator of the tree.
+    #[inline]
+    pub fn separator(&self) -> &str {
+        &self.separator
+    }
+
     /// Inserts a path and data into tree.
-    pub fn insert(&mut self, mut path: &str, data: T) -> &mut Self {
-        let mut next = true;
-        let mut node = &mut self.root;
-        let mut params: Option<Vec<String>> = None;
+    ///
+    /// The data of an existing route with the same path is replaced.
+    pub fn insert(&mut self, path: &str, data: T) -> &mut Self {
+        self.insert_node(path).data = Some(data);
+        self
+    }
 
-        let mut most = 0;
+    /// Inserts a path and data into tree, or returns an error if the path is
+    /// already taken by a route, even one with other param names, if a param
+    /// name is used twice or if a catch-all parameter is not at the end.
+    ///
+    /// ```
+    /// use path_tree::{Error, PathTree};
+    ///
+    /// let mut tree = PathTree::new();
+    /// tree.try_insert("/users/:id", 0).unwrap();
+    ///
+    /// assert_eq!(
+    ///     tree.try_insert("/users/:user_id", 1).unwrap_err(),
+    ///     Error::Conflict {
+    ///         path: "/users/:user_id".to_owned(),
+    ///         existing: "/users/:id".to_owned(),
+    ///     }
+    /// );
+    /// assert_eq!(tree.find("/users/fundon").unwrap().0, &0);
+    /// ```
+    pub fn try_insert(&mut self, path: &str, data: T) -> Result<&mut Self, Error> {
+        validate(path, &self.separator)?;
 
-        path = path.trim_start_matches('/');
+        let (node, params) = locate(&mut self.root, &mut self.params, path, &self.separator);
+        let sep = &*self.separator;
 
-        if path.is_empty() {
-            node.data.replace(data);
-            return self;
+        if node.data.is_some() {
+            return Err(Error::Conflict {
+                path: path.to_owned(),
+                existing: rename(
+                    &normalize(path, sep),
+                    node.params.as_deref().unwrap_or_default(),
+                    sep,
+                ),
+            });
         }
 
-        while next {
-            match path.chars().position(has_colon_or_star) {
-                Some(i) => {
-                    let kind: NodeKind;
-                    let i = char_offset(path, i);
-                    let (mut prefix, mut suffix) = path.split_at(i);
+        node.data = Some(data);
+        node.params = params;
 
-                    if !prefix.is_empty() {
-                        node = node.add_node_static(prefix);
-                    }
+        Ok(self)
+    }
 
-                    prefix = &suffix[..1];
-                    suffix = &suffix[1..];
+    /// Builds a tree from a list of routes, checked like [`PathTree::try_insert`].
+    ///
+    /// The routes are inserted in order and all the errors are returned at
+    /// once, in the order of the routes. A conflict is reported for the later
+    /// of the two routes.
+    ///
+    /// ```
+    /// use path_tree::{Error, PathTree};
+    ///
+    /// let tree = PathTree::build([("/users/:id", 0), ("/users", 1)]).unwrap();
+    /// assert_eq!(tree.find("/users/42").unwrap().0, &0);
+    ///
+    /// let errors = PathTree::build([("/:b", 0), ("/:a", 1), ("/*x/y", 2)]).unwrap_err();
+    /// assert_eq!(
+    ///     errors,
+    ///     [
+    ///         Error::Conflict {
+    ///             path: "/:a".to_owned(),
+    ///             existing: "/:b".to_owned(),
+    ///         },
+    ///         Error::CatchAllNotLast {
+    ///             path: "/*x/y".to_owned(),
+    ///         },
+    ///     ]
+    /// );
+    /// ```
+    pub fn build<I, P>(routes: I) -> Result<Self, Vec<Error>>
+    where
+        I: IntoIterator<Item = (P, T)>,
+        P: AsRef<str>,
+    {
+        let mut tree = Self::new();
+        let errors = routes
+            .into_iter()
+            .filter_map(|(path, data)| tree.try_insert(path.as_ref(), data).err())
+            .collect::<Vec<_>>();
 
-                    let c = prefix.chars().next().unwrap();
-                    if c == ':' {
-                        match suffix.chars().position(has_star_or_slash) {
-                            Some(i) => {
-                                let i = char_offset(suffix, i);
-                                path = &suffix[i..];
-                                suffix = &suffix[..i];
-                            }
-                            None => {
-                                next = false;
+        if errors.is_empty() {
+            Ok(tree)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Removes the route of the path and returns its data.
+    ///
+    /// The path is matched like a pattern, param names are ignored like
+    /// [`PathTree::insert`] does. Nodes left without routes are dropped.
+    ///
+    /// ```
+    /// use path_tree::PathTree;
+    ///
+    /// let mut tree = PathTree::new();
+    /// tree.insert("/users/:id", 0).insert("/users/new", 1);
+    ///
+    /// assert_eq!(tree.remove("/users/:user_id"), Some(0));
+    /// assert_eq!(tree.remove("/users/:id"), None);
+    /// assert_eq!(tree.find("/users/42"), None);
+    /// assert_eq!(tree.find("/users/new").unwrap().0, &1);
+    /// ```
+    pub fn remove(&mut self, path: &str) -> Option<T> {
+        let sep = &*self.separator;
+        self.root.remove(path.trim_start_matches(sep), sep)
+    }
+
+    /// Returns the node of the path, creating the missing nodes on the way.
+    pub(crate) fn insert_node(&mut self, path: &str) -> &mut Node<T> {
+        let (node, params) = self.locate(path);
+        node.params = params;
+        node
+    }
+
+    /// Returns the node of the path and the param names of the path,
+    /// creating the missing nodes on the way.
+    #[inline]
+    fn locate(&mut self, path: &str) -> (&mut Node<T>, Option<Vec<String>>) {
+        locate(&mut self.root, &mut self.params, path, &self.separator)
+    }
+
+    /// Grafts the routes of other tree under the prefix.
+    ///
+    /// The patterns are concatenated, the `/` route of other tree becomes
+    /// `{prefix}/`. Static nodes are merged, the prefix params are prepended to
+    /// the params of the mounted routes. Routes taken by this tree are kept,
+    /// the mounted ones are reported as conflicts, the others are still mounted.
+    ///
+    /// ```
+    /// use path_tree::PathTree;
+    ///
+    /// let mut users = PathTree::new();
+    /// users.insert("/users", 0).insert("/users/:id", 1);
+    ///
+    /// let mut tree = PathTree::new();
+    /// tree.insert("/", 2);
+    /// tree.mount("/api/:version", users).unwrap();
+    ///
+    /// let r = tree.find("/api/v1/users/fundon").unwrap();
+    /// assert_eq!(r.0, &1);
+    /// assert_eq!(r.1, vec![("version", "v1"), ("id", "fundon")]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the trees have different separators.
+    pub fn mount(&mut self, prefix: &str, other: PathTree<T>) -> Result<&mut Self, Vec<Error>> {
+        let sep = &*self.separator;
+
+        assert_eq!(
+            sep, other.separator,
+            "can not mount a tree with another separator"
+        );
+
+        let prefix = prefix.trim_end_matches(sep);
+        let mut skeleton = normalize(prefix, sep);
+
+        if skeleton.contains('*') {
+            return Err(vec![Error::CatchAllNotLast {
+                path: prefix.to_owned(),
+            }]);
+        }
+
+        let (mut node, names) = locate(&mut self.root, &mut self.params, prefix, sep);
+        let names = names.unwrap_or_default();
+
+        let root = other.root;
+        if !prefix.trim_start_matches(sep).is_empty() {
+            if let NodeKind::Static(ref s) = root.kind {
+                node = node.add_node_static(s);
+                skeleton.push_str(s);
+            }
+        }
+
+        let mut errors = Vec::new();
+        graft(node, root, &names, sep, &mut skeleton, &mut errors);
+
+        if other.params + names.len() > self.params {
+            self.params = other.params + names.len();
+        }
+
+        if errors.is_empty() {
+            Ok(self)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Grafts the routes of other tree under the prefix and returns the tree,
+    /// see [`PathTree::mount`].
+    ///
+    /// ```
+    /// use path_tree::PathTree;
+    ///
+    /// let mut repos = PathTree::new();
+    /// repos.insert("/:repo", 0);
+    ///
+    /// let tree = PathTree::new().nest("/repos/:owner", repos).unwrap();
+    ///
+    /// let r = tree.find("/repos/viz-rs/path-tree").unwrap();
+    /// assert_eq!(r.1, vec![("owner", "viz-rs"), ("repo", "path-tree")]);
+    /// ```
+    pub fn nest(mut self, prefix: &str, other: PathTree<T>) -> Result<Self, Vec<Error>> {
+        self.mount(prefix, other)?;
+        Ok(self)
+    }
+
+    /// Merges the routes of other tree into this tree, see [`PathTree::mount`].
+    #[inline]
+    pub fn merge(&mut self, other: PathTree<T>) -> Result<&mut Self, Vec<Error>> {
+        self.mount("", other)
+    }
+
+    /// Returns a reference to the node data and params corresponding to the path.
+    pub fn find<'a>(&'a self, path: &'a str) -> Option<(&'a T, Vec<(&'a str, &'a str)>)> {
+        self.root
+            .find_with_separator(path, &self.separator, self.params)
+            .and_then(|(node, params)| node.data.as_ref().map(|data| (data, params)))
+    }
+
+    /// Returns a reference to the node data corresponding to the path, the
+    /// params are written into the buffer instead of a new `Vec`.
+    ///
+    /// The buffer is cleared first, and left empty if no route matches.
+    ///
+    /// ```
+    /// use path_tree::{ArrayParams, PathTree};
+    ///
+    /// let mut tree = PathTree::new();
+    /// tree.insert("/users/:id", 0);
+    ///
+    /// let mut params = ArrayParams::<4>::new();
+    /// assert_eq!(tree.find_into("/users/42", &mut params), Some(&0));
+    /// assert_eq!(&*params, [("id", "42")]);
+    /// ```
+    pub fn find_into<'a, P>(&'a self, path: &'a str, params: &mut P) -> Option<&'a T>
+    where
+        P: ParamsBuf<'a>,
+    {
+        let data = self
+            .root
+            .find_into(path, &self.separator, params)
+            .and_then(|node| node.data.as_ref());
+
+        if data.is_none() {
+            params.truncate(0);
+        }
+        data
+    }
+
+    /// Returns a reference to the node data and params corresponding to the
+    /// path given as bytes, like a raw request target which may not be UTF-8.
+    ///
+    /// Static segments are compared byte by byte, the values of params are
+    /// returned as they are in the path.
+    ///
+    /// ```
+    /// use path_tree::PathTree;
+    ///
+    /// let mut tree = PathTree::new();
+    /// tree.insert("/files/:name", 0);
+    ///
+    /// let (data, params) = tree.find_bytes(b"/files/\xff.txt").unwrap();
+    /// assert_eq!(data, &0);
+    /// assert_eq!(params, [("name", &b"\xff.txt"[..])]);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn find_bytes<'a>(&'a self, path: &'a [u8]) -> Option<(&'a T, Vec<(&'a str, &'a [u8])>)> {
+        self.root
+            .find_bytes(path, &self.separator, self.params)
+            .and_then(|(node, params)| node.data.as_ref().map(|data| (data, params)))
+    }
+
+    /// Returns the most params of a route, the capacity a params buffer needs.
+    #[inline]
+    pub fn max_params(&self) -> usize {
+        self.params
+    }
+
+    /// Returns an iterator over the pattern and data of every route.
+    ///
+    /// Patterns are rebuilt from the tree, they start with a single separator.
+    ///
+    /// ```
+    /// use path_tree::PathTree;
+    ///
+    /// let mut tree = PathTree::new();
+    /// tree.insert("users/:id", 0).insert("/", 1).insert("/users", 2);
+    ///
+    /// let routes = tree.iter().collect::<Vec<_>>();
+    /// assert_eq!(
+    ///     routes,
+    ///     [
+    ///         ("/".to_owned(), &1),
+    ///         ("/users".to_owned(), &2),
+    ///         ("/users/:id".to_owned(), &0),
+    ///     ]
+    /// );
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            stack: vec![(&self.root, 0)],
+            trail: Vec::new(),
+        }
+    }
+
+    /// Returns an iterator over every route matching the path.
+    ///
+    /// Routes are yielded lazily in the order the tree tries them,
+    /// `Static` -> `Named` -> `Catch-All`, so the first one is the route
+    /// returned by [`PathTree::find`].
+    ///
+    /// ```
+    /// use path_tree::PathTree;
+    ///
+    /// let mut tree = PathTree::new();
+    /// tree.insert("/users/:id", 0);
+    /// tree.insert("/users/*any", 1);
+    /// tree.insert("/*any", 2);
+    ///
+    /// let mut matches = tree.find_all("/users/fundon");
+    /// assert_eq!(
+    ///     matches.next(),
+    ///     Some((&0, vec![("id", "fundon")], "/users/:id".to_owned()))
+    /// );
+    /// assert_eq!(
+    ///     matches.next(),
+    ///     Some((&1, vec![("any", "fundon")], "/users/*any".to_owned()))
+    /// );
+    /// assert_eq!(
+    ///     matches.next(),
+    ///     Some((&2, vec![("any", "users/fundon")], "/*any".to_owned()))
+    /// );
+    /// assert_eq!(matches.next(), None);
+    /// ```
+    #[inline]
+    pub fn find_all<'a>(&'a self, path: &'a str) -> Matches<'a, T> {
+        Matches::new(&self.root, path, &self.separator)
+    }
+
+    /// Returns the data of the route corresponding to the path together with
+    /// the data of every route that is a prefix of it, outermost first, and
+    /// the params of the route.
+    ///
+    /// A prefix is either a catch-all route branching off the traversed nodes
+    /// or a route ending at a segment boundary on the way, which allows attaching
+    /// middleware to whole subtrees. The data of a [`Scope`] comes before the
+    /// data of the routes at its prefix.
+    ///
+    /// ```
+    /// use path_tree::PathTree;
+    ///
+    /// let mut tree = PathTree::new();
+    /// tree.insert("/api/*", "api");
+    /// tree.insert("/api/admin/*", "admin");
+    /// tree.insert("/api/admin/users/:id", "user");
+    ///
+    /// let (data, params) = tree.find_with_ancestors("/api/admin/users/42").unwrap();
+    /// assert_eq!(data, [&"api", &"admin", &"user"]);
+    /// assert_eq!(params, [("id", "42")]);
+    ///
+    /// let (data, params) = tree.find_with_ancestors("/api/admin/settings").unwrap();
+    /// assert_eq!(data, [&"api", &"admin"]);
+    /// assert_eq!(params, [("", "settings")]);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn find_with_ancestors<'a>(
+        &'a self,
+        path: &'a str,
+    ) -> Option<(Vec<&'a T>, Vec<(&'a str, &'a str)>)> {
+        let sep = &*self.separator;
+        let mut walk = Walk::new(&self.root, path, sep);
+        let node = walk.next_node()?;
+        let trail = &walk.trail;
+        let mut data = Vec::new();
+
+        for (i, n) in trail[..trail.len() - 1].iter().enumerate() {
+            let next = trail[i + 1];
+            let boundary = match (&n.kind, &next.kind) {
+                (NodeKind::Static(s), _) if s.ends_with(sep) => true,
+                (_, NodeKind::Static(s)) => s.starts_with(sep),
+                _ => false,
+            };
+
+            if boundary {
+                data.extend(n.scope.as_ref());
+                data.extend(n.data.as_ref());
+            }
+
+            if let Some(c) = n.catch_all().filter(|c| !core::ptr::eq(*c, next)) {
+                data.extend(c.scope.as_ref());
+                data.extend(c.data.as_ref());
+            }
+        }
+
+        data.extend(node.scope.as_ref());
+        data.push(node.data.as_ref()?);
+
+        Some((
+            data,
+            node.params
+                .as_deref()
+                .unwrap_or_default()
+                .iter()
+                .map(String::as_str)
+                .zip(walk.values())
+                .collect(),
+        ))
+    }
+}
+
+/// Inserts every route like [`PathTree::insert`].
+impl<P, T> Extend<(P, T)> for PathTree<T>
+where
+    P: AsRef<str>,
+{
+    fn extend<I>(&mut self, routes: I)
+    where
+        I: IntoIterator<Item = (P, T)>,
+    {
+        for (path, data) in routes {
+            self.insert(path.as_ref(), data);
+        }
+    }
+}
+
+/// Creates a tree and inserts every route like [`PathTree::insert`].
+///
+/// ```
+/// use path_tree::PathTree;
+///
+/// let tree = ["/", "/users/:id"]
+///     .into_iter()
+///     .enumerate()
+///     .map(|(i, r)| (r, i))
+///     .collect::<PathTree<_>>();
+///
+/// assert_eq!(tree.find("/users/42").unwrap().0, &1);
+/// ```
+impl<P, T> FromIterator<(P, T)> for PathTree<T>
+where
+    P: AsRef<str>,
+{
+    fn from_iter<I>(routes: I) -> Self
+    where
+        I: IntoIterator<Item = (P, T)>,
+    {
+        let mut tree = Self::new();
+        tree.extend(routes);
+        tree
+    }
+}
+
+impl<'a, T> IntoIterator for &'a PathTree<T> {
+    type Item = (String, &'a T);
+    type IntoIter = Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the pattern and data of every route in a tree.
+///
+/// This struct is created by [`PathTree::iter`].
+#[derive(Debug)]
+pub struct Iter<'a, T> {
+    stack: Vec<(&'a Node<T>, usize)>,
+    trail: Vec<&'a Node<T>>,
+}
+
+impl<'a, T> Iter<'a, T> {
+    /// Returns the next node with data, the nodes on the way to it are left
+    /// in `trail`.
+    pub(crate) fn next_node(&mut self) -> Option<&'a Node<T>> {
+        while let Some((node, depth)) = self.stack.pop() {
+            self.trail.truncate(depth);
+            self.trail.push(node);
+
+            if let Some(nodes) = node.nodes.as_ref() {
+                self.stack
+                    .extend(nodes.iter().rev().map(|n| (n, depth + 1)));
+            }
+
+            if node.data.is_some() {
+                return Some(node);
+            }
+        }
+
+        None
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = (String, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.next_node()?;
+        Some((pattern(&self.trail), node.data.as_ref()?))
+    }
+}
+
+/// An iterator over the data, params and pattern of every route matching a path.
+///
+/// This struct is created by [`PathTree::find_all`].
+#[derive(Debug)]
+pub struct Matches<'a, T, K: Link = Owned> {
+    walk: Walk<'a, T, K, &'a str>,
+}
+
+impl<'a, T, K: Link> Matches<'a, T, K> {
+    #[inline]
+    pub(crate) fn new(root: &'a Node<T, K>, p: &'a str, sep: &'a str) -> Self {
+        Self {
+            walk: Walk::new(root, p, sep),
+        }
+    }
+}
+
+impl<'a, T, K: Link> Iterator for Matches<'a, T, K> {
+    type Item = (&'a T, Vec<(&'a str, &'a str)>, String);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.walk.next_node()?;
+        let names = node.params.as_deref().unwrap_or_default();
+
+        Some((
+            node.data.as_ref().map(K::data)?,
+            names
+                .iter()
+                .map(String::as_str)
+                .zip(self.walk.values())
+                .collect(),
+            pattern(&self.walk.trail),
+        ))
+    }
+}
+
+impl<'a, T, K: Link> Walk<'a, T, K, &'a str> {
+    /// Returns the values of the params of the last matched node.
+    pub(crate) fn values(&self) -> impl Iterator<Item = &'a str> + '_ {
+        let path = self.path;
+        self.params
+            .iter()
+            .map(move |(start, end)| &path[*start..*end])
+    }
+}
+
+/// Walks the nodes of every route matching a path, which it holds so the
+/// path can be built for the walk.
+#[derive(Debug)]
+pub(crate) struct Walk<'a, T, K: Link, P> {
+    path: P,
+    sep: &'a str,
+    /// The path leaves out the leading separator of the root.
+    bare: bool,
+    stack: Vec<Frame<'a, T, K>>,
+    /// The ranges of the values of the params in the path.
+    params: Vec<(usize, usize)>,
+    trail: Vec<&'a Node<T, K>>,
+}
+
+#[derive(Debug)]
+enum Frame<'a, T, K: Link> {
+    /// Visits a node with the rest of the path, from the byte offset `at`.
+    Visit {
+        node: &'a Node<T, K>,
+        at: usize,
+        params: usize,
+        trail: usize,
+    },
+    /// Yields a node matched by its parent.
+    Found {
+        node: &'a Node<T, K>,
+        params: usize,
+        trail: usize,
+    },
+}
+
+impl<'a, T, K: Link, P: AsRef<str>> Walk<'a, T, K, P> {
+    pub(crate) fn new(root: &'a Node<T, K>, path: P, sep: &'a str) -> Self {
+        let bare = match root.kind {
+            NodeKind::Static(ref s) => {
+                matcher::is_bare_root(s.as_bytes(), sep) && !path.as_ref().starts_with(s)
+            }
+            _ => false,
+        };
+
+        Self {
+            path,
+            sep,
+            bare,
+            stack: vec![Frame::Visit {
+                node: root,
+                at: 0,
+                params: 0,
+                trail: 0,
+            }],
+            params: Vec::new(),
+            trail: Vec::new(),
+        }
+    }
+
+    /// Returns the next matched node, the values and the nodes on the way to it
+    /// are left in `params` and `trail`.
+    pub(crate) fn next_node(&mut self) -> Option<&'a Node<T, K>> {
+        let path = self.path.as_ref();
+
+        while let Some(frame) = self.stack.pop() {
+            let (node, at) = match frame {
+                Frame::Visit {
+                    node,
+                    at,
+                    params,
+                    trail,
+                } => {
+                    self.params.truncate(params);
+                    self.trail.truncate(trail);
+                    self.trail.push(node);
+                    (node, at)
+                }
+                Frame::Found {
+                    node,
+                    params,
+                    trail,
+                } => {
+                    self.params.truncate(params);
+                    self.trail.truncate(trail);
+                    self.trail.push(node);
+                    return Some(node);
+                }
+            };
+            let p = &path[at..];
+
+            match node.kind {
+                NodeKind::Static(ref s) => {
+                    let l = if core::mem::take(&mut self.bare) {
+                        0
+                    } else if s.is_empty() || !p.starts_with(s.as_str()) {
+                        continue;
+                    } else {
+                        s.len()
+                    };
+
+                    let params = self.params.len();
+                    let trail = self.trail.len();
+
+                    // Pushed in reverse, the stack pops them in priority order.
+                    if l == p.len() {
+                        // Ended `/` `/*any`
+                        if s.ends_with(self.sep) {
+                            if let Some(n) = node.catch_all() {
+                                self.stack.push(Frame::Found {
+                                    node: n,
+                                    params,
+                                    trail,
+                                });
                             }
                         }
-                        kind = NodeKind::Parameter;
-                    } else {
-                        next = false;
-                        kind = NodeKind::CatchAll;
+                        if node.data.is_some() {
+                            self.stack.push(Frame::Found {
+                                node,
+                                params,
+                                trail: trail - 1,
+                            });
+                        }
+                        continue;
                     }
-                    most += 1;
-                    params.get_or_insert_with(Vec::new).push(suffix.to_owned());
-                    node = node.add_node_dynamic(c, kind);
+
+                    let at = at + l;
+                    let c = path[at..].chars().next().unwrap();
+
+                    let mut visit = |n: Option<&'a Node<T, K>>| {
+                        if let Some(node) = n {
+                            self.stack.push(Frame::Visit {
+                                node,
+                                at,
+                                params,
+                                trail,
+                            });
+                        }
+                    };
+
+                    // Catch-All Parameter
+                    visit(node.catch_all());
+                    // Named Parameter
+                    visit(node.param());
+                    // Static
+                    visit(node.child(c));
                 }
-                None => {
-                    next = false;
-                    node = node.add_node_static(path);
+                NodeKind::Parameter => match p.find_sep(self.sep) {
+                    Some(i) => {
+                        if let Some(n) = node.child(p[i..].chars().next().unwrap()) {
+                            self.params.push((at, at + i));
+                            self.stack.push(Frame::Visit {
+                                node: n,
+                                at: at + i,
+                                params: self.params.len(),
+                                trail: self.trail.len(),
+                            });
+                        }
+                    }
+                    None if node.data.is_some() => {
+                        self.params.push((at, path.len()));
+                        return Some(node);
+                    }
+                    None => {}
+                },
+                NodeKind::CatchAll => {
+                    if node.data.is_some() {
+                        self.params.push((at, path.len()));
+                        return Some(node);
+                    }
                 }
             }
         }
 
-        if most > self.params {
-            self.params = most;
+        None
+    }
+}
+
+/// Moves the data and children of `src` into `dst`, which is at the same path.
+///
+/// `skeleton` is the pattern of `dst` without param names.
+fn graft<T>(
+    dst: &mut Node<T>,
+    src: Node<T>,
+    prefix: &[String],
+    sep: &str,
+    skeleton: &mut String,
+    errors: &mut Vec<Error>,
+) {
+    let len = skeleton.len();
+
+    if let Some(scope) = src.scope {
+        if dst.scope.is_some() {
+            errors.push(Error::Conflict {
+                path: skeleton.clone(),
+                existing: skeleton.clone(),
+            });
+        } else {
+            dst.scope = Some(scope);
         }
+    }
 
-        node.data = Some(data);
-        node.params = params;
+    if let Some(data) = src.data {
+        let mut names = prefix.to_vec();
+        names.extend(src.params.unwrap_or_default());
 
-        self
+        if let Some(name) = names[prefix.len()..]
+            .iter()
+            .find(|name| !name.is_empty() && prefix.contains(name))
+        {
+            errors.push(Error::DuplicateParam {
+                path: rename(skeleton, &names, sep),
+                name: name.clone(),
+            });
+        } else if dst.data.is_some() {
+            errors.push(Error::Conflict {
+                path: rename(skeleton, &names, sep),
+                existing: rename(skeleton, dst.params.as_deref().unwrap_or_default(), sep),
+            });
+        } else {
+            dst.data = Some(data);
+            dst.params = Some(names).filter(|names| !names.is_empty());
+        }
     }
 
-    /// Returns a reference to the node data and params corresponding to the path.
-    pub fn find<'a>(&'a self, path: &'a str) -> Option<(&'a T, Vec<(&'a str, &'a str)>)> {
-        self.root
-            .find_with_capacity(path, self.params)
-            .and_then(|(node, params)| node.data.as_ref().map(|data| (data, params)))
+    for (c, node) in src
+        .indices
+        .unwrap_or_default()
+        .into_iter()
+        .zip(src.nodes.unwrap_or_default())
+    {
+        let child = match node.kind {
+            NodeKind::Static(ref s) => {
+                skeleton.push_str(s);
+                dst.add_node_static(s)
+            }
+            ref kind => {
+                skeleton.push(char::from(c));
+                dst.add_node_dynamic(char::from(c), kind.clone())
+            }
+        };
+
+        graft(child, node, prefix, sep, skeleton, errors);
+        skeleton.truncate(len);
+    }
+}
+
+/// Checks that the param names are unique and the catch-all is at the end.
+fn validate(path: &str, sep: &str) -> Result<(), Error> {
+    let mut names = Vec::new();
+
+    for (c, range) in params(path, sep) {
+        let name = &path[range];
+
+        if c == b'*' && name.contains(sep) {
+            return Err(Error::CatchAllNotLast {
+                path: path.to_owned(),
+            });
+        }
+        if !name.is_empty() && names.contains(&name) {
+            return Err(Error::DuplicateParam {
+                path: path.to_owned(),
+                name: name.to_owned(),
+            });
+        }
+        names.push(name);
+    }
+
+    Ok(())
+}
+
+/// Returns the kind, `:` or `*`, and the range of the name of every param of
+/// the pattern. The name of a catch-all parameter is the rest of the pattern.
+fn params<'a>(pattern: &'a str, sep: &'a str) -> impl Iterator<Item = (u8, Range<usize>)> + 'a {
+    let mut at = 0;
+
+    iter::from_fn(move || {
+        let i = at + find_param(&pattern[at..], sep)?;
+        let c = pattern.as_bytes()[i];
+        let start = i + 1;
+
+        at = if c == b'*' {
+            pattern.len()
+        } else {
+            param_end(&pattern[start..], sep).map_or(pattern.len(), |end| start + end)
+        };
+
+        Some((c, start..at))
+    })
+}
+
+/// Returns the byte offset of the first param of the pattern, a `:` starting
+/// a separator is not one.
+fn find_param(pattern: &str, sep: &str) -> Option<usize> {
+    let mut i = 0;
+
+    while let Some(c) = pattern[i..].chars().next() {
+        if pattern[i..].starts_with(sep) {
+            i += sep.len();
+        } else if has_colon_or_star(c) {
+            return Some(i);
+        } else {
+            i += c.len_utf8();
+        }
+    }
+
+    None
+}
+
+/// Returns the byte offset of the end of a param name, at a `*` or a separator.
+fn param_end(name: &str, sep: &str) -> Option<usize> {
+    name.char_indices()
+        .find(|(i, c)| *c == '*' || name[*i..].starts_with(sep))
+        .map(|(i, _)| i)
+}
+
+/// Checks that the separator is not empty, `:` or has no `*`, which would
+/// make patterns ambiguous.
+fn is_separator(sep: &str) -> bool {
+    !sep.is_empty() && sep != ":" && !sep.contains('*')
+}
+
+/// Returns the path with a single leading separator.
+fn normalize(path: &str, sep: &str) -> String {
+    let mut normalized = String::with_capacity(path.len() + sep.len());
+    normalized.push_str(sep);
+    normalized.push_str(path.trim_start_matches(sep));
+    normalized
+}
+
+/// Returns the node of the pattern below the root and the param names of the
+/// pattern, creating the missing nodes on the way, and raises the most params
+/// of a route.
+fn locate<'a, T>(
+    root: &'a mut Node<T>,
+    most: &mut usize,
+    path: &str,
+    sep: &str,
+) -> (&'a mut Node<T>, Option<Vec<String>>) {
+    let (node, params) = root.locate(path, sep);
+
+    let len = params.as_ref().map_or(0, Vec::len);
+    if len > *most {
+        *most = len;
+    }
+
+    (node, params)
+}
+
+/// Replaces the param names in the pattern.
+fn rename(pattern: &str, names: &[String], sep: &str) -> String {
+    let mut renamed = String::with_capacity(pattern.len());
+    let mut names = names.iter().map(String::as_str);
+    let mut at = 0;
+
+    for (_, range) in params(pattern, sep) {
+        renamed.push_str(&pattern[at..range.start]);
+        renamed.push_str(names.next().unwrap_or_default());
+        at = range.end;
+    }
+    renamed.push_str(&pattern[at..]);
+
+    renamed
+}
+
+/// Rebuilds the pattern of the route ending at the last node.
+fn pattern<T, K: Link>(trail: &[&Node<T, K>]) -> String {
+    let mut names = trail
+        .last()
+        .and_then(|node| node.params.as_deref())
+        .unwrap_or_default()
+        .iter();
+    let mut pattern = String::new();
+
+    for node in trail {
+        match node.kind {
+            NodeKind::Static(ref s) => pattern.push_str(s),
+            NodeKind::Parameter => pattern.push(':'),
+            NodeKind::CatchAll => pattern.push('*'),
+        }
+        if !matches!(node.kind, NodeKind::Static(_)) {
+            pattern.push_str(names.next().map_or("", String::as_str));
+        }
     }
+
+    pattern
 }
 
 #[inline]
@@ -382,14 +1515,27 @@ const fn has_colon_or_star(c: char) -> bool {
     (c == ':') | (c == '*')
 }
 
+/// Returns the index of the static child starting with the char.
 #[inline]
-const fn has_star_or_slash(c: char) -> bool {
-    (c == '*') | (c == '/')
+fn position<T, K: Link>(indices: &[u8], nodes: &[K::Child<T>], c: char) -> Option<usize> {
+    let b = first_byte(c);
+
+    indices.iter().enumerate().position(|(i, x)| {
+        *x == b && matches!(K::child(&nodes[i]).kind, NodeKind::Static(ref s) if s.starts_with(c))
+    })
+}
+
+/// Returns the index of the parameter child of the kind.
+#[inline]
+fn dynamic<T, K: Link>(nodes: &[K::Child<T>], kind: &NodeKind) -> Option<usize> {
+    nodes.iter().position(|n| K::child(n).kind == *kind)
 }
 
 #[inline]
-fn position(p: &[char], c: char) -> Option<usize> {
-    p.iter().position(|x| *x == c)
+fn first_byte(c: char) -> u8 {
+    let mut buf = [0; 4];
+    c.encode_utf8(&mut buf);
+    buf[0]
 }
 
 #[inline]
@@ -400,8 +1546,3 @@ fn loc(s: &str, p: &str) -> usize {
         .map(|(c, _)| c.len_utf8())
         .sum()
 }
-
-#[inline]
-fn char_offset(p: &str, i: usize) -> usize {
-    p.chars().take(i).map(|c| c.len_utf8()).sum()
-}
@@ -0,0 +1,87 @@
+use alloc::sync::Arc;
+
+use crate::Node;
+
+/// How a node holds its data and children.
+pub trait Link: Sized {
+    /// The data of a node.
+    type Data<T>;
+
+    /// A child of a node.
+    type Child<T>;
+
+    /// Returns the data.
+    fn data<T>(data: &Self::Data<T>) -> &T;
+
+    /// Returns the child.
+    fn child<T>(child: &Self::Child<T>) -> &Node<T, Self>;
+
+    /// Returns the child to change, copying it first if it is shared.
+    fn child_mut<T>(child: &mut Self::Child<T>) -> &mut Node<T, Self>;
+
+    /// Wraps a node to add it as a child.
+    fn new_child<T>(node: Node<T, Self>) -> Self::Child<T>;
+}
+
+/// A node owning its data and children, the nodes of [`PathTree`].
+///
+/// [`PathTree`]: crate::PathTree
+#[derive(Debug)]
+pub struct Owned;
+
+/// A node sharing its data and children with the copies of the node, the
+/// nodes of [`PersistentPathTree`].
+///
+/// [`PersistentPathTree`]: crate::PersistentPathTree
+#[derive(Debug)]
+pub struct Shared;
+
+impl Link for Owned {
+    type Data<T> = T;
+    type Child<T> = Node<T, Self>;
+
+    #[inline]
+    fn data<T>(data: &T) -> &T {
+        data
+    }
+
+    #[inline]
+    fn child<T>(child: &Node<T, Self>) -> &Node<T, Self> {
+        child
+    }
+
+    #[inline]
+    fn child_mut<T>(child: &mut Node<T, Self>) -> &mut Node<T, Self> {
+        child
+    }
+
+    #[inline]
+    fn new_child<T>(node: Node<T, Self>) -> Node<T, Self> {
+        node
+    }
+}
+
+impl Link for Shared {
+    type Data<T> = Arc<T>;
+    type Child<T> = Arc<Node<T, Self>>;
+
+    #[inline]
+    fn data<T>(data: &Arc<T>) -> &T {
+        data
+    }
+
+    #[inline]
+    fn child<T>(child: &Arc<Node<T, Self>>) -> &Node<T, Self> {
+        child
+    }
+
+    #[inline]
+    fn child_mut<T>(child: &mut Arc<Node<T, Self>>) -> &mut Node<T, Self> {
+        Arc::make_mut(child)
+    }
+
+    #[inline]
+    fn new_child<T>(node: Node<T, Self>) -> Arc<Node<T, Self>> {
+        Arc::new(node)
+    }
+}
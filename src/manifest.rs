@@ -0,0 +1,504 @@
+//! Route manifests in TOML, JSON or YAML.
+//!
+//! A manifest is a list of routes with a pattern, the methods it serves, an
+//! optional unique name and arbitrary metadata. The routes of a pattern serve
+//! different methods. Each format is enabled by the feature of the same name.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "toml")]
+//! # {
+//! use path_tree::{
+//!     manifest::{Format, Route},
+//!     PathTree,
+//! };
+//!
+//! let tree = PathTree::<Vec<Route<toml::Value>>>::from_manifest(
+//!     r#"
+//! [[routes]]
+//! pattern = "/users/:id"
+//! methods = ["GET", "PUT"]
+//! name = "user"
+//! metadata = { auth = true }
+//!
+//! [[routes]]
+//! pattern = "/users/:id"
+//! methods = ["DELETE"]
+//! "#,
+//!     Format::Toml,
+//! )
+//! .unwrap();
+//!
+//! let (routes, params) = tree.find("/users/42").unwrap();
+//! let route = routes.iter().find(|r| r.serves("PUT")).unwrap();
+//! assert_eq!(route.name.as_deref(), Some("user"));
+//! assert_eq!(route.methods, ["GET", "PUT"]);
+//! assert_eq!(params, [("id", "42")]);
+//! # }
+//! ```
+
+#[cfg(feature = "toml")]
+use alloc::borrow::ToOwned;
+#[cfg(any(feature = "json", feature = "yaml"))]
+use alloc::{borrow::Cow, string::ToString};
+use alloc::{string::String, vec, vec::Vec};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{Error, PathTree};
+
+/// A route of a manifest, the data of the tree built from it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Route<M> {
+    /// The pattern of the route.
+    pub pattern: String,
+    /// The methods served by the route, empty for every method.
+    #[serde(default = "Vec::new")]
+    pub methods: Vec<String>,
+    /// The name of the route, unique in the manifest.
+    pub name: Option<String>,
+    /// The metadata of the route.
+    pub metadata: Option<M>,
+}
+
+impl<M> Route<M> {
+    /// Returns `true` if the route serves the method.
+    pub fn serves(&self, method: &str) -> bool {
+        self.methods.is_empty() || self.methods.iter().any(|m| m == method)
+    }
+
+    /// Returns `true` if both routes serve a method.
+    fn overlaps(&self, other: &Self) -> bool {
+        other.methods.is_empty() || other.methods.iter().any(|m| self.serves(m))
+    }
+}
+
+#[derive(Deserialize)]
+struct Manifest<M> {
+    routes: Vec<Route<M>>,
+}
+
+/// The format of a manifest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Format {
+    /// TOML, with a `[[routes]]` table per route.
+    #[cfg(feature = "toml")]
+    Toml,
+
+    /// JSON, with a `routes` array.
+    #[cfg(feature = "json")]
+    Json,
+
+    /// YAML, with a `routes` sequence.
+    #[cfg(feature = "yaml")]
+    Yaml,
+}
+
+impl Format {
+    fn parse<M>(self, src: &str) -> Result<Manifest<M>, ManifestError>
+    where
+        M: DeserializeOwned,
+    {
+        let (line, message) = match self {
+            #[cfg(feature = "toml")]
+            Self::Toml => match toml::from_str(src) {
+                Ok(manifest) => return Ok(manifest),
+                Err(e) => (
+                    e.span().map(|span| line_of(src, span.start)),
+                    e.message().to_owned(),
+                ),
+            },
+            #[cfg(feature = "json")]
+            Self::Json => match serde_json::from_str(src) {
+                Ok(manifest) => return Ok(manifest),
+                Err(e) => (Some(e.line()), e.to_string()),
+            },
+            #[cfg(feature = "yaml")]
+            Self::Yaml => match serde_yaml::from_str(src) {
+                Ok(manifest) => return Ok(manifest),
+                Err(e) => (e.location().map(|l| l.line()), e.to_string()),
+            },
+        };
+
+        Err(ManifestError {
+            line,
+            kind: ErrorKind::Parse(message),
+        })
+    }
+
+    /// Returns the line of the pattern of every route.
+    fn lines(self, src: &str) -> Vec<Option<usize>> {
+        match self {
+            #[cfg(feature = "toml")]
+            Self::Toml => match toml::from_str::<Spans>(src) {
+                Ok(spans) => spans
+                    .routes
+                    .iter()
+                    .map(|r| Some(line_of(src, r.pattern.span().start)))
+                    .collect(),
+                Err(_) => Vec::new(),
+            },
+            #[cfg(feature = "json")]
+            Self::Json => self.borrowed(src, serde_json::from_str(src).ok()),
+            #[cfg(feature = "yaml")]
+            Self::Yaml => self.borrowed(src, serde_yaml::from_str(src).ok()),
+        }
+    }
+
+    /// Locates the patterns borrowed from the manifest by their offset, the
+    /// ones with escapes are probed.
+    #[cfg(any(feature = "json", feature = "yaml"))]
+    fn borrowed(self, src: &str, patterns: Option<Patterns<'_>>) -> Vec<Option<usize>> {
+        let routes = patterns.map(|p| p.routes).unwrap_or_default();
+
+        routes
+            .iter()
+            .enumerate()
+            .map(|(i, route)| match route.pattern {
+                Cow::Borrowed(s) => Some(line_of(src, s.as_ptr() as usize - src.as_ptr() as usize)),
+                Cow::Owned(_) => self.probe(src, i),
+            })
+            .collect()
+    }
+
+    /// Returns the line of the pattern of the `i`th route, deserializing the
+    /// manifest up to it.
+    #[cfg(any(feature = "json", feature = "yaml"))]
+    fn probe(self, src: &str, i: usize) -> Option<usize> {
+        match self {
+            #[cfg(feature = "toml")]
+            Self::Toml => None,
+            #[cfg(feature = "json")]
+            Self::Json => {
+                let mut de = serde_json::Deserializer::from_str(src);
+                match probe::pattern(&mut de, i) {
+                    Err(e) if e.line() > 0 => Some(e.line()),
+                    _ => None,
+                }
+            }
+            #[cfg(feature = "yaml")]
+            Self::Yaml => probe::pattern(serde_yaml::Deserializer::from_str(src), i)
+                .err()?
+                .location()
+                .map(|l| l.line()),
+        }
+    }
+}
+
+/// The span of every pattern of a TOML manifest.
+#[cfg(feature = "toml")]
+#[derive(Deserialize)]
+struct Spans {
+    routes: Vec<PatternSpan>,
+}
+
+#[cfg(feature = "toml")]
+#[derive(Deserialize)]
+struct PatternSpan {
+    pattern: toml::Spanned<String>,
+}
+
+/// The pattern of every route of a JSON or YAML manifest, borrowed from it
+/// when it has no escapes.
+#[cfg(any(feature = "json", feature = "yaml"))]
+#[derive(Deserialize)]
+struct Patterns<'a> {
+    #[serde(borrow)]
+    routes: Vec<BorrowedPattern<'a>>,
+}
+
+#[cfg(any(feature = "json", feature = "yaml"))]
+#[derive(Deserialize)]
+struct BorrowedPattern<'a> {
+    #[serde(borrow)]
+    pattern: Cow<'a, str>,
+}
+
+/// Locates the patterns of JSON and YAML manifests from the location of an
+/// error.
+#[cfg(any(feature = "json", feature = "yaml"))]
+mod probe {
+    use alloc::string::String;
+    use core::fmt;
+
+    use serde::{
+        de::{DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor},
+        Deserializer,
+    };
+
+    /// Deserializes the manifest up to the pattern of the `i`th route and
+    /// fails there.
+    pub(super) fn pattern<'de, D>(deserializer: D, i: usize) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Probe(i).deserialize(deserializer)
+    }
+
+    /// Fails at the pattern of the `i`th route, the location of the error is the
+    /// location of the pattern.
+    struct Probe(usize);
+
+    impl<'de> DeserializeSeed<'de> for Probe {
+        type Value = ();
+
+        fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_map(self)
+        }
+    }
+
+    impl<'de> Visitor<'de> for Probe {
+        type Value = ();
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a manifest")
+        }
+
+        fn visit_map<A>(self, mut map: A) -> Result<(), A::Error>
+        where
+            A: MapAccess<'de>,
+        {
+            while let Some(key) = map.next_key::<String>()? {
+                match key.as_str() {
+                    "routes" => map.next_value_seed(Routes(self.0))?,
+                    _ => map.next_value::<IgnoredAny>().map(drop)?,
+                }
+            }
+            Ok(())
+        }
+    }
+
+    /// Skips the routes before the `i`th one.
+    struct Routes(usize);
+
+    impl<'de> DeserializeSeed<'de> for Routes {
+        type Value = ();
+
+        fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_seq(self)
+        }
+    }
+
+    impl<'de> Visitor<'de> for Routes {
+        type Value = ();
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a list of routes")
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<(), A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            for _ in 0..self.0 {
+                if seq.next_element::<IgnoredAny>()?.is_none() {
+                    return Ok(());
+                }
+            }
+            seq.next_element_seed(Pattern)?;
+            while seq.next_element::<IgnoredAny>()?.is_some() {}
+            Ok(())
+        }
+    }
+
+    /// Skips the fields of the route before its pattern.
+    struct Pattern;
+
+    impl<'de> DeserializeSeed<'de> for Pattern {
+        type Value = ();
+
+        fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_map(self)
+        }
+    }
+
+    impl<'de> Visitor<'de> for Pattern {
+        type Value = ();
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a route")
+        }
+
+        fn visit_map<A>(self, mut map: A) -> Result<(), A::Error>
+        where
+            A: MapAccess<'de>,
+        {
+            while let Some(key) = map.next_key::<String>()? {
+                match key.as_str() {
+                    "pattern" => map.next_value_seed(Found)?,
+                    _ => map.next_value::<IgnoredAny>().map(drop)?,
+                }
+            }
+            Ok(())
+        }
+    }
+
+    /// Fails at the pattern, it accepts no value.
+    struct Found;
+
+    impl<'de> DeserializeSeed<'de> for Found {
+        type Value = ();
+
+        fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_any(self)
+        }
+    }
+
+    impl<'de> Visitor<'de> for Found {
+        type Value = ();
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("no value")
+        }
+    }
+}
+
+/// An invalid manifest.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ManifestError {
+    /// The line of the error, starting at `1`.
+    pub line: Option<usize>,
+    /// What is wrong.
+    pub kind: ErrorKind,
+}
+
+/// The kind of a [`ManifestError`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The manifest can not be parsed.
+    Parse(String),
+
+    /// The route is rejected by [`PathTree::try_insert`].
+    Route(Error),
+
+    /// The method is not a valid HTTP method token.
+    Method(String),
+
+    /// The name is already used by another route.
+    DuplicateName(String),
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "line {}: ", line)?;
+        }
+
+        match self.kind {
+            ErrorKind::Parse(ref message) => f.write_str(message),
+            ErrorKind::Route(ref e) => e.fmt(f),
+            ErrorKind::Method(ref method) => write!(f, "`{}` is not a valid method", method),
+            ErrorKind::DuplicateName(ref name) => {
+                write!(f, "the name `{}` is used more than once", name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ManifestError {}
+
+impl<M> PathTree<Vec<Route<M>>>
+where
+    M: DeserializeOwned,
+{
+    /// Builds a tree from a manifest, every route is checked like
+    /// [`PathTree::try_insert`] and all the errors are returned at once.
+    ///
+    /// The routes of a pattern are kept in the order of the manifest, a route
+    /// serving a method of an earlier one with the same pattern conflicts
+    /// with it. The line of a route error is the line of its pattern.
+    pub fn from_manifest(src: &str, format: Format) -> Result<Self, Vec<ManifestError>> {
+        let manifest = format.parse::<M>(src).map_err(|e| vec![e])?;
+
+        let mut names = HashSet::new();
+        let mut errors = Vec::new();
+        // The index of the first route of every pattern and its routes.
+        let mut patterns = Vec::<(usize, Vec<Route<M>>)>::new();
+        let mut positions = HashMap::<String, usize>::new();
+
+        for (i, route) in manifest.routes.into_iter().enumerate() {
+            for method in &route.methods {
+                if method.is_empty() || !method.bytes().all(is_token) {
+                    errors.push((i, ErrorKind::Method(method.clone())));
+                }
+            }
+
+            if let Some(ref name) = route.name {
+                if !names.insert(name.clone()) {
+                    errors.push((i, ErrorKind::DuplicateName(name.clone())));
+                }
+            }
+
+            match positions.get(&route.pattern) {
+                Some(&p) => {
+                    let routes = &mut patterns[p].1;
+                    if routes.iter().any(|r| r.overlaps(&route)) {
+                        let e = Error::Conflict {
+                            path: route.pattern.clone(),
+                            existing: route.pattern,
+                        };
+                        errors.push((i, ErrorKind::Route(e)));
+                    } else {
+                        routes.push(route);
+                    }
+                }
+                None => {
+                    positions.insert(route.pattern.clone(), patterns.len());
+                    patterns.push((i, vec![route]));
+                }
+            }
+        }
+
+        let mut tree = PathTree::new();
+        for (i, routes) in patterns {
+            let pattern = routes[0].pattern.clone();
+            if let Err(e) = tree.try_insert(&pattern, routes) {
+                errors.push((i, ErrorKind::Route(e)));
+            }
+        }
+
+        if errors.is_empty() {
+            return Ok(tree);
+        }
+
+        // Stable, the errors of a route keep their order.
+        errors.sort_by_key(|(i, _)| *i);
+        let lines = format.lines(src);
+        Err(errors
+            .into_iter()
+            .map(|(i, kind)| ManifestError {
+                line: lines.get(i).copied().flatten(),
+                kind,
+            })
+            .collect())
+    }
+}
+
+/// Returns the line of a byte offset, starting at `1`.
+fn line_of(src: &str, offset: usize) -> usize {
+    src[..offset].matches('\n').count() + 1
+}
+
+/// Checks the `tchar` rule of RFC 9110.
+fn is_token(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)
+}
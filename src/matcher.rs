@@ -0,0 +1,260 @@
+use alloc::string::String;
+
+use crate::{explain::Trace, link::Link, Haystack, Node, NodeKind, ParamsBuf, Reason};
+
+/// The fewest children of a node dispatched by a table, fewer are scanned.
+pub(crate) const DISPATCH: usize = 4;
+
+/// The kind of a node seen by the matcher.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum Kind<'a> {
+    /// A static node with its segment.
+    Static(&'a [u8]),
+    Parameter,
+    CatchAll,
+}
+
+/// A node of a tree, as the matcher walks it.
+///
+/// The trees store their nodes differently, they share the lookup through
+/// this trait.
+pub(crate) trait Cursor<'a>: Copy {
+    /// Returns the kind of the node.
+    fn kind(self) -> Kind<'a>;
+
+    /// Returns `true` if a route ends at the node.
+    fn has_data(self) -> bool;
+
+    /// Returns the param names of the route ending at the node.
+    fn names(self) -> Option<&'a [String]>;
+
+    /// Returns the static children whose segment starts with the byte, in
+    /// the order they are tried.
+    fn statics(self, b: u8) -> impl Iterator<Item = Self>;
+
+    /// Returns the named parameter child.
+    fn param(self) -> Option<Self>;
+
+    /// Returns the catch-all parameter child.
+    fn catch_all(self) -> Option<Self>;
+}
+
+impl<'a, T, K: Link> Cursor<'a> for &'a Node<T, K> {
+    #[inline]
+    fn kind(self) -> Kind<'a> {
+        match self.kind {
+            NodeKind::Static(ref s) => Kind::Static(s.as_bytes()),
+            NodeKind::Parameter => Kind::Parameter,
+            NodeKind::CatchAll => Kind::CatchAll,
+        }
+    }
+
+    #[inline]
+    fn has_data(self) -> bool {
+        self.data.is_some()
+    }
+
+    #[inline]
+    fn names(self) -> Option<&'a [String]> {
+        self.params.as_deref()
+    }
+
+    #[inline]
+    fn statics(self, b: u8) -> impl Iterator<Item = Self> {
+        let indices = self.indices.as_deref().unwrap_or_default();
+        let nodes = self.nodes.as_deref().unwrap_or_default();
+
+        let range = match self.table.as_deref().map(|table| table[b as usize]) {
+            Some(0) => 0..0,
+            Some(i) if i != crate::MANY => i as usize - 1..i as usize,
+            _ => 0..indices.len(),
+        };
+
+        range
+            .filter(move |i| indices[*i] == b)
+            .map(move |i| K::child(&nodes[i]))
+            .filter(|n| matches!(n.kind, NodeKind::Static(_)))
+    }
+
+    #[inline]
+    fn param(self) -> Option<Self> {
+        Node::param(self)
+    }
+
+    #[inline]
+    fn catch_all(self) -> Option<Self> {
+        Node::catch_all(self)
+    }
+}
+
+/// Returns the node matching the path below `node`, the params are written
+/// into the buffer and named after the route of the node.
+pub(crate) fn find<'a, N, H, P, V>(
+    node: N,
+    p: &'a H,
+    sep: &str,
+    params: &mut P,
+    trace: &mut V,
+) -> Option<N>
+where
+    N: Cursor<'a>,
+    H: Haystack + ?Sized,
+    P: ParamsBuf<'a, H>,
+    V: Trace<'a, N, H>,
+{
+    let node = match node.kind() {
+        // The leading separator of a root may be left out of the path.
+        Kind::Static(s) if is_bare_root(s, sep) && !p.raw().starts_with(s) => {
+            trace.visit(node, p);
+            let found = find_static(node, s, 0, p, sep, params, trace);
+            trace.leave(found);
+            found.ok()
+        }
+        _ => find_inner(node, p, sep, params, trace),
+    };
+    if params.overflowed() {
+        params.truncate(0);
+        return None;
+    }
+
+    match node.and_then(Cursor::names) {
+        Some(names) => {
+            for (name, (key, _)) in names.iter().zip(params.as_mut_slice()) {
+                *key = name;
+            }
+        }
+        None => params.truncate(0),
+    }
+
+    node
+}
+
+#[inline]
+fn find_inner<'a, N, H, P, V>(
+    node: N,
+    p: &'a H,
+    sep: &str,
+    params: &mut P,
+    trace: &mut V,
+) -> Option<N>
+where
+    N: Cursor<'a>,
+    H: Haystack + ?Sized,
+    P: ParamsBuf<'a, H>,
+    V: Trace<'a, N, H>,
+{
+    trace.visit(node, p);
+    let found = find_node(node, p, sep, params, trace);
+    trace.leave(found);
+    found.ok()
+}
+
+fn find_node<'a, N, H, P, V>(
+    node: N,
+    p: &'a H,
+    sep: &str,
+    params: &mut P,
+    trace: &mut V,
+) -> Result<N, Reason>
+where
+    N: Cursor<'a>,
+    H: Haystack + ?Sized,
+    P: ParamsBuf<'a, H>,
+    V: Trace<'a, N, H>,
+{
+    match node.kind() {
+        Kind::Static(s) => {
+            // A byte-exact prefix ends at a char boundary of the path.
+            if s.is_empty() || !p.raw().starts_with(s) {
+                Err(Reason::Prefix)
+            } else {
+                find_static(node, s, s.len(), p, sep, params, trace)
+            }
+        }
+        Kind::Parameter => match p.find_sep(sep) {
+            Some(i) => {
+                let (value, p) = p.cut(i);
+                params.push(("", value));
+
+                // Only one child starts with the separator.
+                node.statics(p.raw()[0])
+                    .next()
+                    .and_then(|n| find_inner(n, p, sep, params, trace))
+                    .ok_or(Reason::NoChild)
+            }
+            None if node.names().is_some() => {
+                params.push(("", p));
+                Ok(node)
+            }
+            None => Err(Reason::NoRoute),
+        },
+        Kind::CatchAll => {
+            params.push(("", p));
+            Ok(node)
+        }
+    }
+}
+
+/// Matches the rest of the path after its first `l` bytes, which matched
+/// the static segment `s` of the node.
+fn find_static<'a, N, H, P, V>(
+    node: N,
+    s: &[u8],
+    l: usize,
+    p: &'a H,
+    sep: &str,
+    params: &mut P,
+    trace: &mut V,
+) -> Result<N, Reason>
+where
+    N: Cursor<'a>,
+    H: Haystack + ?Sized,
+    P: ParamsBuf<'a, H>,
+    V: Trace<'a, N, H>,
+{
+    if l == p.raw().len() {
+        return if node.has_data() {
+            Ok(node)
+        } else if s.ends_with(sep.as_bytes()) {
+            // Fixed: has only route `/*`
+            // Ended `/` `/*any`
+            node.catch_all().ok_or(Reason::NoRoute)
+        } else {
+            Err(Reason::NoRoute)
+        };
+    }
+
+    let p = p.cut(l).1;
+    let b = p.raw()[0];
+
+    // Drops the values pushed by a failed branch.
+    let len = params.len();
+
+    // Static, the chars of many children can start with the same byte.
+    for n in node.statics(b) {
+        if let Some(n) = find_inner(n, p, sep, params, trace) {
+            return Ok(n);
+        }
+        params.truncate(len);
+    }
+
+    // Named Parameter
+    if let Some(n) = node.param() {
+        if let Some(n) = find_inner(n, p, sep, params, trace) {
+            return Ok(n);
+        }
+        params.truncate(len);
+    }
+
+    // Catch-All Parameter
+    node.catch_all()
+        .and_then(|n| find_inner(n, p, sep, params, trace))
+        .ok_or(Reason::NoChild)
+}
+
+/// Checks if the segment of a root is a separator which paths may leave out,
+/// any but `/` which starts every HTTP path.
+#[inline]
+pub(crate) fn is_bare_root(s: &[u8], sep: &str) -> bool {
+    sep != "/" && s == sep.as_bytes()
+}
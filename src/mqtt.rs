@@ -0,0 +1,168 @@
+//! MQTT topic filter matching.
+//!
+//! Topic filters are stored in a [`PathTree`], `+` becomes a named parameter
+//! and `#` becomes a catch-all parameter. The `:` and `*` of filters and topics
+//! are escaped, so they are matched like any other char.
+//!
+//! # Examples
+//!
+//! ```
+//! use path_tree::mqtt::TopicTree;
+//!
+//! let mut tree = TopicTree::new();
+//! tree.insert("sensors/+/temp", 0).unwrap();
+//! tree.insert("sensors/#", 1).unwrap();
+//! tree.insert("#", 2).unwrap();
+//!
+//! let subscribers = tree.find_all("sensors/kitchen/temp").map(|(_, n)| *n).collect::<Vec<_>>();
+//! assert_eq!(subscribers, [0, 1, 2]);
+//!
+//! // `$` topics are not matched by filters starting with a wildcard.
+//! assert_eq!(tree.find_all("$SYS/uptime").count(), 0);
+//!
+//! tree.insert("clock/12:00", 3).unwrap();
+//! assert_eq!(tree.find_all("clock/12:00").next(), Some(("clock/12:00", &3)));
+//! ```
+
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+use core::fmt;
+use core::iter;
+
+use crate::{PathTree, Walk};
+
+/// An invalid MQTT topic filter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FilterError {
+    /// The filter is empty.
+    Empty,
+
+    /// A `+` or `#` shares its level with other characters,
+    /// or `#` is not the last level.
+    MisplacedWildcard,
+}
+
+impl fmt::Display for FilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Empty => "topic filter is empty",
+            Self::MisplacedWildcard => "wildcard must occupy an entire level, `#` must be last",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FilterError {}
+
+/// A tree of MQTT topic filters and their subscribers.
+#[derive(Clone, Debug)]
+pub struct TopicTree<T> {
+    tree: PathTree<Vec<usize>>,
+    subscribers: Vec<(String, T)>,
+}
+
+impl<T> Default for TopicTree<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> TopicTree<T> {
+    /// Creates an empty topic tree.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            tree: PathTree::new(),
+            subscribers: Vec::new(),
+        }
+    }
+
+    /// Subscribes data to a topic filter.
+    ///
+    /// A filter can have many subscribers, they are returned in insertion order.
+    pub fn insert(&mut self, filter: &str, data: T) -> Result<&mut Self, FilterError> {
+        let path = encode_filter(filter)?;
+
+        self.tree
+            .insert_node(&path)
+            .data
+            .get_or_insert_with(Vec::new)
+            .push(self.subscribers.len());
+        self.subscribers.push((filter.to_owned(), data));
+
+        Ok(self)
+    }
+
+    /// Returns the filter and data of every subscriber matching the topic.
+    ///
+    /// The subscribers are found lazily, in the order the tree tries the
+    /// filters.
+    pub fn find_all<'a>(&'a self, topic: &str) -> impl Iterator<Item = (&'a str, &'a T)> {
+        let mut walk = (!topic.is_empty() && !topic.contains(['+', '#'])).then(|| {
+            // Topics are encoded the same way as filters.
+            let mut path = String::with_capacity(topic.len() + 3);
+            path.push_str("/.");
+            escape(&mut path, topic);
+            path.push('/');
+            Walk::new(&self.tree.root, path, "/")
+        });
+        let mut ids = [].iter();
+
+        let system = topic.starts_with('$');
+
+        iter::from_fn(move || loop {
+            match ids.next() {
+                Some(id) => return Some(*id),
+                None => ids = walk.as_mut()?.next_node()?.data.as_deref()?.iter(),
+            }
+        })
+        .map(move |id| {
+            let (filter, data) = &self.subscribers[id];
+            (filter.as_str(), data)
+        })
+        .filter(move |(filter, _)| !(system && filter.starts_with(['+', '#'])))
+    }
+}
+
+/// Translates a topic filter into a path-tree pattern.
+///
+/// A `.` is prepended so that an empty first level survives the leading `/`
+/// trimming of [`PathTree::insert`], and every level but `#` gets a trailing `/`
+/// so that `+` matches an empty last level. The other levels are escaped.
+fn encode_filter(filter: &str) -> Result<String, FilterError> {
+    if filter.is_empty() {
+        return Err(FilterError::Empty);
+    }
+
+    let mut path = String::with_capacity(filter.len() + 3);
+    path.push_str("/.");
+
+    let mut levels = filter.split('/').peekable();
+    while let Some(level) = levels.next() {
+        match level {
+            "+" => path.push(':'),
+            "#" if levels.peek().is_none() => {
+                path.push('*');
+                break;
+            }
+            _ if level.contains(['+', '#']) => return Err(FilterError::MisplacedWildcard),
+            _ => escape(&mut path, level),
+        }
+        path.push('/');
+    }
+
+    Ok(path)
+}
+
+/// Escapes the `:` and `*` the tree reserves for parameters, and the `%` of
+/// the escapes.
+fn escape(path: &mut String, s: &str) {
+    for c in s.chars() {
+        match c {
+            ':' => path.push_str("%3A"),
+            '*' => path.push_str("%2A"),
+            '%' => path.push_str("%25"),
+            _ => path.push(c),
+        }
+    }
+}
@@ -0,0 +1,443 @@
+//! OpenAPI 3 paths.
+//!
+//! A [`Document`] can be deserialized from JSON or YAML with any serde format
+//! crate, and serialized back from [`PathTree::to_openapi`]. Routes are keyed
+//! by method and path, `GET /users/{id}` becomes `/GET/users/:id` like in
+//! `examples/hello.rs`.
+//!
+//! # Examples
+//!
+//! ```
+//! use path_tree::{openapi::Document, PathTree};
+//!
+//! let document: Document = serde_json::from_str(
+//!     r#"{
+//!         "openapi": "3.0.0",
+//!         "paths": {
+//!             "/users/{id}": {
+//!                 "get": { "operationId": "getUser" },
+//!                 "delete": { "operationId": "deleteUser" }
+//!             }
+//!         }
+//!     }"#,
+//! )
+//! .unwrap();
+//!
+//! let tree = PathTree::from_openapi(&document).unwrap();
+//!
+//! let (operation, params) = tree.find("/DELETE/users/42").unwrap();
+//! assert_eq!(operation.operation_id.as_deref(), Some("deleteUser"));
+//! assert_eq!(operation.path, "/users/{id}");
+//! assert_eq!(params, [("id", "42")]);
+//! ```
+
+use alloc::{
+    borrow::ToOwned,
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, NodeKind, PathTree};
+
+/// The part of an OpenAPI document describing paths.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Document {
+    /// The path items by path template.
+    #[serde(default)]
+    pub paths: BTreeMap<String, PathItem>,
+}
+
+/// The operations of a path template.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PathItem {
+    /// The `GET` operation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub get: Option<Operation>,
+    /// The `PUT` operation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub put: Option<Operation>,
+    /// The `POST` operation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post: Option<Operation>,
+    /// The `DELETE` operation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delete: Option<Operation>,
+    /// The `OPTIONS` operation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<Operation>,
+    /// The `HEAD` operation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub head: Option<Operation>,
+    /// The `PATCH` operation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub patch: Option<Operation>,
+    /// The `TRACE` operation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<Operation>,
+    /// The parameters shared by the operations, or references to them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<ReferenceOr<Parameter>>,
+}
+
+/// The methods of the operations of a path item.
+const METHODS: [&str; 8] = [
+    "GET", "PUT", "POST", "DELETE", "OPTIONS", "HEAD", "PATCH", "TRACE",
+];
+
+impl PathItem {
+    /// Returns the method and operation of every operation.
+    pub fn operations(&self) -> impl Iterator<Item = (&'static str, &Operation)> {
+        METHODS
+            .into_iter()
+            .zip([
+                &self.get,
+                &self.put,
+                &self.post,
+                &self.delete,
+                &self.options,
+                &self.head,
+                &self.patch,
+                &self.trace,
+            ])
+            .filter_map(|(method, operation)| Some((method, operation.as_ref()?)))
+    }
+
+    fn operation_mut(&mut self, method: &str) -> Option<&mut Option<Operation>> {
+        Some(match method {
+            "GET" => &mut self.get,
+            "PUT" => &mut self.put,
+            "POST" => &mut self.post,
+            "DELETE" => &mut self.delete,
+            "OPTIONS" => &mut self.options,
+            "HEAD" => &mut self.head,
+            "PATCH" => &mut self.patch,
+            "TRACE" => &mut self.trace,
+            _ => return None,
+        })
+    }
+}
+
+/// An object of a document, or a reference to one.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ReferenceOr<T> {
+    /// A reference to an object defined elsewhere, like
+    /// `#/components/parameters/id`.
+    Reference {
+        /// The URI of the object.
+        #[serde(rename = "$ref")]
+        reference: String,
+    },
+
+    /// The object itself.
+    Item(T),
+}
+
+impl<T> ReferenceOr<T> {
+    /// Returns the object, unless it is a reference.
+    pub fn as_item(&self) -> Option<&T> {
+        match self {
+            Self::Reference { .. } => None,
+            Self::Item(item) => Some(item),
+        }
+    }
+}
+
+/// A parameter of a path item.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Parameter {
+    /// The name of the parameter.
+    pub name: String,
+    /// The location of the parameter, `path` for the params of a route.
+    #[serde(rename = "in")]
+    pub location: String,
+    /// Whether the parameter is required, always `true` in the path.
+    #[serde(default)]
+    pub required: bool,
+    /// The schema of the parameter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<Schema>,
+}
+
+/// The schema of a parameter.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Schema {
+    /// The type of the value.
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub ty: Option<String>,
+    /// The regular expression the value matches.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+}
+
+/// An operation of a path item.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Operation {
+    /// The unique name of the operation.
+    #[serde(
+        rename = "operationId",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub operation_id: Option<String>,
+}
+
+/// The data of a route imported from an OpenAPI document.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OperationRef {
+    /// The method of the operation, in uppercase.
+    pub method: &'static str,
+    /// The path template of the operation, as written in the document.
+    pub path: String,
+    /// The unique name of the operation.
+    pub operation_id: Option<String>,
+}
+
+/// A path of an OpenAPI document which can not be imported.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum OpenApiError {
+    /// The path template has a parameter which does not fill a whole segment,
+    /// or contains `:` or `*`.
+    Template {
+        /// The path template.
+        path: String,
+    },
+
+    /// The route of an operation is rejected by [`PathTree::try_insert`].
+    Route(Error),
+}
+
+impl fmt::Display for OpenApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Template { path } => write!(f, "unsupported path template `{}`", path),
+            Self::Route(e) => e.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OpenApiError {}
+
+impl PathTree<OperationRef> {
+    /// Builds a tree from the operations of an OpenAPI document.
+    ///
+    /// Every operation is inserted at `/{METHOD}{path}` with its `{param}`
+    /// templates translated to `:param`, all the errors are returned at once.
+    pub fn from_openapi(document: &Document) -> Result<Self, Vec<OpenApiError>> {
+        let mut tree = PathTree::new();
+        let mut errors = Vec::new();
+
+        for (path, item) in &document.paths {
+            let pattern = match translate(path) {
+                Some(pattern) => pattern,
+                None => {
+                    errors.push(OpenApiError::Template { path: path.clone() });
+                    continue;
+                }
+            };
+
+            for (method, operation) in item.operations() {
+                let route = OperationRef {
+                    method,
+                    path: path.clone(),
+                    operation_id: operation.operation_id.clone(),
+                };
+
+                if let Err(e) = tree.try_insert(&format!("/{}{}", method, pattern), route) {
+                    errors.push(OpenApiError::Route(e));
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(tree)
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl<T> PathTree<T> {
+    /// Exports the routes of the tree as an OpenAPI paths object, with the
+    /// operation of a route built by `f`.
+    ///
+    /// Routes keyed by method like [`PathTree::from_openapi`] become the
+    /// operation of their method, other routes become a path item without
+    /// operations. Every param is declared as a required string, a named
+    /// parameter can not contain the separator and a catch-all parameter
+    /// becomes a `{param}` template too. Unnamed params are called `param1`,
+    /// `param2` and so on.
+    ///
+    /// ```
+    /// use path_tree::{
+    ///     openapi::{Operation, Parameter, ReferenceOr, Schema},
+    ///     PathTree,
+    /// };
+    ///
+    /// let mut tree = PathTree::new();
+    /// tree.insert("/GET/users/:id", "getUser")
+    ///     .insert("/DELETE/users/:id", "deleteUser");
+    ///
+    /// let document = tree.to_openapi(|id| Operation {
+    ///     operation_id: Some(id.to_string()),
+    /// });
+    ///
+    /// let item = &document.paths["/users/{id}"];
+    /// assert_eq!(item.get.as_ref().unwrap().operation_id.as_deref(), Some("getUser"));
+    /// assert_eq!(item.delete.as_ref().unwrap().operation_id.as_deref(), Some("deleteUser"));
+    /// assert_eq!(
+    ///     item.parameters,
+    ///     [ReferenceOr::Item(Parameter {
+    ///         name: "id".to_owned(),
+    ///         location: "path".to_owned(),
+    ///         required: true,
+    ///         schema: Some(Schema {
+    ///             ty: Some("string".to_owned()),
+    ///             pattern: Some("^[^/]*$".to_owned()),
+    ///         }),
+    ///     })]
+    /// );
+    /// ```
+    pub fn to_openapi<F>(&self, mut f: F) -> Document
+    where
+        F: FnMut(&T) -> Operation,
+    {
+        let mut document = Document::default();
+        let mut routes = self.iter();
+
+        while let Some(node) = routes.next_node() {
+            let mut names = node.params.as_deref().unwrap_or_default().iter();
+            let mut template = String::new();
+            let mut parameters = Vec::new();
+
+            for n in &routes.trail {
+                let pattern = match n.kind {
+                    NodeKind::Static(ref s) => {
+                        template.push_str(s);
+                        continue;
+                    }
+                    NodeKind::Parameter => Some(without(&self.separator)),
+                    NodeKind::CatchAll => None,
+                };
+
+                let name = match names.next() {
+                    Some(name) if !name.is_empty() => name.clone(),
+                    _ => format!("param{}", parameters.len() + 1),
+                };
+
+                template.push('{');
+                template.push_str(&name);
+                template.push('}');
+
+                parameters.push(ReferenceOr::Item(Parameter {
+                    name,
+                    location: "path".to_owned(),
+                    required: true,
+                    schema: Some(Schema {
+                        ty: Some("string".to_owned()),
+                        pattern,
+                    }),
+                }));
+            }
+
+            let (method, path) = split_method(&template, &self.separator);
+            let item = document.paths.entry(path.to_owned()).or_default();
+
+            if let Some(operation) = method.and_then(|m| item.operation_mut(m)) {
+                *operation = Some(f(node.data.as_ref().unwrap()));
+            }
+            if item.parameters.is_empty() {
+                item.parameters = parameters;
+            }
+        }
+
+        document
+    }
+}
+
+/// Splits `/{METHOD}/path` into the method and `/path`, the method is one of
+/// the operations of a path item.
+fn split_method<'a>(template: &'a str, sep: &str) -> (Option<&'a str>, &'a str) {
+    let path = template.trim_start_matches(sep);
+    let (method, rest) = path.split_at(path.find(sep).unwrap_or(path.len()));
+
+    if METHODS.contains(&method) {
+        (
+            Some(method),
+            if rest.is_empty() {
+                &template[..sep.len()]
+            } else {
+                rest
+            },
+        )
+    } else {
+        (None, template)
+    }
+}
+
+/// Returns a regular expression matching the values without the separator.
+fn without(sep: &str) -> String {
+    let mut chars = sep.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => format!("^[^{}]*$", escape(c, "\\]^-[")),
+        _ => format!(
+            "^(?:(?!{}).)*$",
+            sep.chars()
+                .map(|c| escape(c, "\\^$.|?*+()[]{}"))
+                .collect::<String>()
+        ),
+    }
+}
+
+/// Escapes a char special in a regular expression.
+fn escape(c: char, special: &str) -> String {
+    if special.contains(c) {
+        format!("\\{}", c)
+    } else {
+        c.to_string()
+    }
+}
+
+/// Translates `{param}` templates to `:param`, a template must fill a whole
+/// segment.
+fn translate(path: &str) -> Option<String> {
+    let mut pattern = String::with_capacity(path.len());
+    let mut rest = path;
+
+    if !path.starts_with('/') {
+        pattern.push('/');
+    }
+
+    while let Some(i) = rest.find(['{', '}', ':', '*']) {
+        // A template starts a segment.
+        let start = rest[..i].ends_with('/') || (i == 0 && pattern.ends_with('/'));
+        if !start {
+            return None;
+        }
+        pattern.push_str(&rest[..i]);
+        rest = rest[i..].strip_prefix('{')?;
+
+        let end = rest.find('}')?;
+        let name = &rest[..end];
+        rest = &rest[end + 1..];
+
+        if name.is_empty()
+            || name.contains(['{', '/', ':', '*'])
+            || !(rest.is_empty() || rest.starts_with('/'))
+        {
+            return None;
+        }
+        pattern.push(':');
+        pattern.push_str(name);
+    }
+
+    pattern.push_str(rest);
+    Some(pattern)
+}
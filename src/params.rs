@@ -0,0 +1,129 @@
+use alloc::vec::Vec;
+use core::ops::Deref;
+
+/// A buffer receiving the params of a lookup, see [`PathTree::find_into`].
+///
+/// The values are `str` by default, `[u8]` for [`PathTree::find_bytes`].
+///
+/// [`PathTree::find_into`]: crate::PathTree::find_into
+/// [`PathTree::find_bytes`]: crate::PathTree::find_bytes
+pub trait ParamsBuf<'a, V: ?Sized = str> {
+    /// Appends a param.
+    fn push(&mut self, param: (&'a str, &'a V));
+
+    /// Returns the number of params.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if there are no params.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Keeps the first `len` params.
+    fn truncate(&mut self, len: usize);
+
+    /// Returns the params as a mutable slice.
+    fn as_mut_slice(&mut self) -> &mut [(&'a str, &'a V)];
+
+    /// Returns `true` if more params were pushed than the buffer holds, a
+    /// lookup then finds no route.
+    #[inline]
+    fn overflowed(&self) -> bool {
+        false
+    }
+}
+
+impl<'a, V: ?Sized> ParamsBuf<'a, V> for Vec<(&'a str, &'a V)> {
+    #[inline]
+    fn push(&mut self, param: (&'a str, &'a V)) {
+        Vec::push(self, param);
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    #[inline]
+    fn truncate(&mut self, len: usize) {
+        Vec::truncate(self, len);
+    }
+
+    #[inline]
+    fn as_mut_slice(&mut self) -> &mut [(&'a str, &'a V)] {
+        self
+    }
+}
+
+/// A fixed-size buffer of params which does not allocate.
+///
+/// `N` should be at least [`PathTree::max_params`] of the tree it is used
+/// with, the params of a route with more are dropped and the lookup finds no
+/// route.
+///
+/// [`PathTree::max_params`]: crate::PathTree::max_params
+#[derive(Clone, Copy, Debug)]
+pub struct ArrayParams<'a, const N: usize> {
+    params: [(&'a str, &'a str); N],
+    /// The number of params pushed, more than `N` if some were dropped.
+    len: usize,
+}
+
+impl<const N: usize> Default for ArrayParams<'_, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> ArrayParams<'_, N> {
+    /// Creates an empty buffer.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            params: [("", ""); N],
+            len: 0,
+        }
+    }
+}
+
+impl<'a, const N: usize> Deref for ArrayParams<'a, N> {
+    type Target = [(&'a str, &'a str)];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.params[..self.len.min(N)]
+    }
+}
+
+impl<'a, const N: usize> ParamsBuf<'a> for ArrayParams<'a, N> {
+    /// The param is dropped if the buffer is full.
+    #[inline]
+    fn push(&mut self, param: (&'a str, &'a str)) {
+        if let Some(slot) = self.params.get_mut(self.len) {
+            *slot = param;
+        }
+        self.len += 1;
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    fn truncate(&mut self, len: usize) {
+        self.len = self.len.min(len);
+    }
+
+    #[inline]
+    fn as_mut_slice(&mut self) -> &mut [(&'a str, &'a str)] {
+        &mut self.params[..self.len.min(N)]
+    }
+
+    #[inline]
+    fn overflowed(&self) -> bool {
+        self.len > N
+    }
+}
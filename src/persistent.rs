@@ -0,0 +1,251 @@
+use crate::{
+    explain, is_separator, link::Shared, matcher, normalize, rename, validate, Error, Explanation,
+    Matches, Node, NodeKind, ParamsBuf, PathTree,
+};
+use alloc::{borrow::ToOwned, string::String, sync::Arc, vec::Vec};
+
+/// A tree whose versions share their unchanged nodes.
+///
+/// [`insert`] and [`remove`] leave the tree as it is and return a new version,
+/// only the nodes on the way to the changed route are copied. Cloning a tree
+/// is cheap, the data is not cloned either.
+///
+/// [`insert`]: PersistentPathTree::insert
+/// [`remove`]: PersistentPathTree::remove
+///
+/// ```
+/// use path_tree::PersistentPathTree;
+///
+/// let base = PersistentPathTree::new()
+///     .insert("/users/:id", "user")
+///     .insert("/about", "about");
+///
+/// let tenant = base.insert("/about", "tenant about");
+///
+/// assert_eq!(base.find("/about").unwrap().0, &"about");
+/// assert_eq!(tenant.find("/about").unwrap().0, &"tenant about");
+/// assert_eq!(tenant.find("/users/42").unwrap().1, [("id", "42")]);
+/// ```
+#[derive(Debug)]
+pub struct PersistentPathTree<T> {
+    root: Arc<Node<T, Shared>>,
+    params: usize,
+    separator: Arc<str>,
+}
+
+impl<T> Clone for PersistentPathTree<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            root: self.root.clone(),
+            params: self.params,
+            separator: self.separator.clone(),
+        }
+    }
+}
+
+impl<T> Default for PersistentPathTree<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Converts a tree, the data attached by scopes is dropped.
+impl<T> From<PathTree<T>> for PersistentPathTree<T> {
+    fn from(tree: PathTree<T>) -> Self {
+        Self {
+            root: Arc::new(Node::from(tree.root)),
+            params: tree.params,
+            separator: tree.separator.into(),
+        }
+    }
+}
+
+impl<T> From<Node<T>> for Node<T, Shared> {
+    fn from(node: Node<T>) -> Self {
+        Self {
+            kind: node.kind,
+            data: node.data.map(Arc::new),
+            scope: None,
+            indices: node.indices,
+            table: node.table,
+            nodes: node
+                .nodes
+                .map(|nodes| nodes.into_iter().map(|n| Arc::new(n.into())).collect()),
+            params: node.params,
+        }
+    }
+}
+
+impl<T> PersistentPathTree<T> {
+    /// Creates a new tree with a root node, like [`PathTree::new`].
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_separator("/")
+    }
+
+    /// Creates a new tree which splits segments by `separator`, like
+    /// [`PathTree::with_separator`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the separator is empty, is `:` or contains `*`.
+    pub fn with_separator(separator: &str) -> Self {
+        assert!(
+            is_separator(separator),
+            "`{}` can not be used as a separator",
+            separator
+        );
+
+        Self {
+            root: Arc::new(Node::new(NodeKind::Static(separator.to_owned()))),
+            params: 0,
+            separator: separator.into(),
+        }
+    }
+
+    /// Returns the segment separator of the tree.
+    #[inline]
+    pub fn separator(&self) -> &str {
+        &self.separator
+    }
+
+    /// Returns a new version with the route, like [`PathTree::insert`].
+    #[must_use]
+    pub fn insert(&self, path: &str, data: T) -> Self {
+        let mut tree = self.clone();
+        let (node, params) = tree.locate(path);
+        node.data = Some(Arc::new(data));
+        node.params = params;
+        tree
+    }
+
+    /// Returns a new version with the route, or an error like
+    /// [`PathTree::try_insert`].
+    pub fn try_insert(&self, path: &str, data: T) -> Result<Self, Error> {
+        let sep = &*self.separator;
+
+        validate(path, sep)?;
+
+        let mut tree = self.clone();
+        let (node, params) = tree.locate(path);
+
+        if node.data.is_some() {
+            return Err(Error::Conflict {
+                path: path.to_owned(),
+                existing: rename(
+                    &normalize(path, sep),
+                    node.params.as_deref().unwrap_or_default(),
+                    sep,
+                ),
+            });
+        }
+
+        node.data = Some(Arc::new(data));
+        node.params = params;
+
+        Ok(tree)
+    }
+
+    /// Returns a new version without the route and the data of the route,
+    /// like [`PathTree::remove`].
+    ///
+    /// ```
+    /// use path_tree::PersistentPathTree;
+    ///
+    /// let v1 = PersistentPathTree::new().insert("/users/:id", 0);
+    /// let (v2, data) = v1.remove("/users/:id");
+    ///
+    /// assert_eq!(data.as_deref(), Some(&0));
+    /// assert_eq!(v1.find("/users/42").unwrap().0, &0);
+    /// assert_eq!(v2.find("/users/42"), None);
+    /// ```
+    pub fn remove(&self, path: &str) -> (Self, Option<Arc<T>>) {
+        let mut tree = self.clone();
+        let sep = &*self.separator;
+
+        match Arc::make_mut(&mut tree.root).remove(path.trim_start_matches(sep), sep) {
+            Some(data) => (tree, Some(data)),
+            // Nothing changed, the copied nodes are dropped.
+            None => (self.clone(), None),
+        }
+    }
+
+    /// Returns a reference to the node data and params corresponding to the
+    /// path, like [`PathTree::find`].
+    pub fn find<'a>(&'a self, path: &'a str) -> Option<(&'a T, Vec<(&'a str, &'a str)>)> {
+        let mut params = Vec::with_capacity(self.params);
+        self.find_into(path, &mut params).map(|data| (data, params))
+    }
+
+    /// Returns a reference to the node data corresponding to the path, the
+    /// params are written into the buffer, like [`PathTree::find_into`].
+    pub fn find_into<'a, P>(&'a self, path: &'a str, params: &mut P) -> Option<&'a T>
+    where
+        P: ParamsBuf<'a>,
+    {
+        let data = self
+            .root
+            .find_into(path, &self.separator, params)
+            .and_then(|node| node.data.as_deref());
+
+        if data.is_none() {
+            params.truncate(0);
+        }
+        data
+    }
+
+    /// Returns a reference to the node data and params corresponding to the
+    /// path given as bytes, like [`PathTree::find_bytes`].
+    #[allow(clippy::type_complexity)]
+    pub fn find_bytes<'a>(&'a self, path: &'a [u8]) -> Option<(&'a T, Vec<(&'a str, &'a [u8])>)> {
+        let mut params = Vec::with_capacity(self.params);
+        matcher::find(&*self.root, path, &self.separator, &mut params, &mut ())
+            .and_then(|node| node.data.as_deref())
+            .map(|data| (data, params))
+    }
+
+    /// Returns an iterator over every route matching the path, like
+    /// [`PathTree::find_all`].
+    #[inline]
+    pub fn find_all<'a>(
+        &'a self,
+        path: &'a str,
+    ) -> impl Iterator<Item = (&'a T, Vec<(&'a str, &'a str)>, String)> + 'a {
+        Matches::<'a, T, Shared>::new(&self.root, path, &self.separator)
+    }
+
+    /// Looks up a path and returns every node tried, like
+    /// [`PathTree::explain`].
+    #[inline]
+    pub fn explain<'a>(&'a self, path: &'a str) -> Explanation<'a> {
+        explain::explain(&self.root, path, &self.separator, self.params)
+    }
+
+    /// Returns the most params of a route, the capacity a params buffer needs.
+    #[inline]
+    pub fn max_params(&self) -> usize {
+        self.params
+    }
+
+    /// Returns `true` if the two versions share their root, no route has
+    /// changed between them.
+    #[inline]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.root, &other.root)
+    }
+
+    /// Returns the node of the path, copying the shared nodes on the way and
+    /// creating the missing ones, and the param names of the path.
+    fn locate(&mut self, path: &str) -> (&mut Node<T, Shared>, Option<Vec<String>>) {
+        let (node, params) = Arc::make_mut(&mut self.root).locate(path, &self.separator);
+
+        let most = params.as_ref().map_or(0, Vec::len);
+        if most > self.params {
+            self.params = most;
+        }
+
+        (node, params)
+    }
+}
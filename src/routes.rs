@@ -0,0 +1,39 @@
+use alloc::string::String;
+/// An enum of routes with typed params, usually derived with
+/// `#[derive(Routes)]` from `path-tree-macros`.
+///
+/// Every variant has a pattern and a field for each of its params, the
+/// fields are parsed from the path and displayed back into it.
+///
+/// ```
+/// use path_tree::Routes;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Route {
+///     User { id: u64 },
+/// }
+///
+/// impl Routes for Route {
+///     fn recognize(path: &str) -> Option<Self> {
+///         let id = path.strip_prefix("/users/")?.parse().ok()?;
+///         Some(Self::User { id })
+///     }
+///
+///     fn to_path(&self) -> String {
+///         match self {
+///             Self::User { id } => format!("/users/{}", id),
+///         }
+///     }
+/// }
+///
+/// assert_eq!(Route::recognize("/users/42"), Some(Route::User { id: 42 }));
+/// assert_eq!(Route::User { id: 42 }.to_path(), "/users/42");
+/// ```
+pub trait Routes: Sized {
+    /// Returns the first route matching the path whose params can be parsed,
+    /// in priority order, or `None`.
+    fn recognize(path: &str) -> Option<Self>;
+
+    /// Returns the path of the route.
+    fn to_path(&self) -> String;
+}
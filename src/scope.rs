@@ -0,0 +1,141 @@
+use crate::{normalize, Error, PathTree};
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+
+/// A group of routes sharing a prefix and optionally some data.
+///
+/// This struct is created by [`PathTree::scope`].
+#[derive(Debug)]
+pub struct Scope<'a, T> {
+    tree: &'a mut PathTree<T>,
+    prefix: String,
+    errors: Vec<Error>,
+}
+
+impl<T> PathTree<T> {
+    /// Inserts a group of routes under the prefix.
+    ///
+    /// The patterns of the scope are appended to the prefix and checked like
+    /// [`PathTree::try_insert`], so a param name can not be used by both the
+    /// prefix and a route. The data of a scope is returned before the data of
+    /// its routes by [`PathTree::find_with_ancestors`].
+    ///
+    /// ```
+    /// use path_tree::PathTree;
+    ///
+    /// let mut tree = PathTree::new();
+    /// tree.scope("/repos/:owner/:repo", |s| {
+    ///     s.data("repo middleware");
+    ///     s.insert("/issues", "issues");
+    ///     s.insert("/pulls/:number", "pull");
+    /// })
+    /// .unwrap();
+    ///
+    /// let r = tree.find("/repos/viz-rs/path-tree/pulls/1").unwrap();
+    /// assert_eq!(r.0, &"pull");
+    /// assert_eq!(
+    ///     r.1,
+    ///     vec![("owner", "viz-rs"), ("repo", "path-tree"), ("number", "1")]
+    /// );
+    ///
+    /// let (data, _) = tree.find_with_ancestors("/repos/viz-rs/path-tree/issues").unwrap();
+    /// assert_eq!(data, [&"repo middleware", &"issues"]);
+    /// ```
+    pub fn scope<F>(&mut self, prefix: &str, f: F) -> Result<&mut Self, Vec<Error>>
+    where
+        F: FnOnce(&mut Scope<'_, T>),
+    {
+        let errors = Scope::new(self, prefix).run(f);
+
+        if errors.is_empty() {
+            Ok(self)
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl<'a, T> Scope<'a, T> {
+    fn new(tree: &'a mut PathTree<T>, prefix: &str) -> Self {
+        let sep = &*tree.separator;
+
+        Self {
+            prefix: normalize(prefix, sep).trim_end_matches(sep).to_owned(),
+            tree,
+            errors: Vec::new(),
+        }
+    }
+
+    fn run<F>(mut self, f: F) -> Vec<Error>
+    where
+        F: FnOnce(&mut Self),
+    {
+        if self.prefix.contains('*') {
+            self.errors.push(Error::CatchAllNotLast {
+                path: self.prefix.clone(),
+            });
+        } else {
+            f(&mut self);
+        }
+
+        self.errors
+    }
+
+    /// Returns the prefix of the scope.
+    #[inline]
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Inserts a path relative to the prefix and data into tree.
+    ///
+    /// An empty path is the prefix itself, `/` is the prefix followed by `/`.
+    /// Errors are returned by [`PathTree::scope`].
+    pub fn insert(&mut self, path: &str, data: T) -> &mut Self {
+        let path = self.path(path);
+
+        if let Err(e) = self.tree.try_insert(&path, data) {
+            self.errors.push(e);
+        }
+
+        self
+    }
+
+    /// Attaches data to the prefix shared by the routes of the scope.
+    pub fn data(&mut self, data: T) -> &mut Self {
+        let node = self.tree.locate(&self.prefix).0;
+
+        if node.scope.is_some() {
+            self.errors.push(Error::Conflict {
+                path: self.prefix.clone(),
+                existing: self.prefix.clone(),
+            });
+        } else {
+            node.scope = Some(data);
+        }
+
+        self
+    }
+
+    /// Inserts a nested group of routes under the prefix.
+    pub fn scope<F>(&mut self, prefix: &str, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut Scope<'_, T>),
+    {
+        let prefix = self.path(prefix);
+        let errors = Scope::new(self.tree, &prefix).run(f);
+
+        self.errors.extend(errors);
+        self
+    }
+
+    fn path(&self, path: &str) -> String {
+        let sep = &*self.tree.separator;
+        let mut joined = self.prefix.clone();
+
+        if !path.is_empty() {
+            joined.push_str(sep);
+            joined.push_str(path.trim_start_matches(sep));
+        }
+        joined
+    }
+}
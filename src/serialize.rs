@@ -0,0 +1,145 @@
+use alloc::{string::String, vec::Vec};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{is_separator, pattern, rename, Error, Node, PathTree};
+
+/// The serialized form of a tree, its routes and scope data by pattern.
+#[derive(Serialize)]
+struct Repr<'a, T> {
+    separator: &'a str,
+    routes: Vec<Route<&'a T>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    scopes: Vec<Route<&'a T>>,
+}
+
+#[derive(Deserialize)]
+struct OwnedRepr<T> {
+    #[serde(default = "separator")]
+    separator: String,
+    routes: Vec<Route<T>>,
+    #[serde(default = "Vec::new")]
+    scopes: Vec<Route<T>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Route<T> {
+    pattern: String,
+    data: T,
+}
+
+fn separator() -> String {
+    "/".into()
+}
+
+/// Serializes the separator, the pattern and data of every route and the
+/// prefix and data of every scope.
+///
+/// The param names of a scope are the ones of the routes below it.
+///
+/// ```
+/// use path_tree::PathTree;
+///
+/// let mut tree = PathTree::new();
+/// tree.insert("/users/:id", 1);
+///
+/// assert_eq!(
+///     serde_json::to_string(&tree).unwrap(),
+///     r#"{"separator":"/","routes":[{"pattern":"/users/:id","data":1}]}"#
+/// );
+/// ```
+impl<T> Serialize for PathTree<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut scopes = Vec::new();
+        collect_scopes(&self.root, &self.separator, &mut Vec::new(), &mut scopes);
+
+        Repr {
+            separator: &self.separator,
+            routes: self
+                .iter()
+                .map(|(pattern, data)| Route { pattern, data })
+                .collect(),
+            scopes,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Rebuilds a tree from its serialized form, routes are inserted like
+/// [`PathTree::insert`] so that every serialized tree can be rebuilt.
+impl<'de, T> Deserialize<'de> for PathTree<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let repr = OwnedRepr::<T>::deserialize(deserializer)?;
+
+        if !is_separator(&repr.separator) {
+            return Err(de::Error::custom(format_args!(
+                "`{}` can not be used as a separator",
+                repr.separator
+            )));
+        }
+
+        let mut tree = PathTree::with_separator(&repr.separator);
+
+        for route in repr.routes {
+            tree.insert(&route.pattern, route.data);
+        }
+
+        for scope in repr.scopes {
+            let node = tree.locate(&scope.pattern).0;
+
+            if node.scope.is_some() {
+                return Err(de::Error::custom(Error::Conflict {
+                    existing: scope.pattern.clone(),
+                    path: scope.pattern,
+                }));
+            }
+            node.scope = Some(scope.data);
+        }
+
+        Ok(tree)
+    }
+}
+
+fn collect_scopes<'a, T>(
+    node: &'a Node<T>,
+    sep: &str,
+    trail: &mut Vec<&'a Node<T>>,
+    scopes: &mut Vec<Route<&'a T>>,
+) {
+    trail.push(node);
+
+    if let Some(data) = node.scope.as_ref() {
+        let names = route_names(node).unwrap_or_default();
+        scopes.push(Route {
+            pattern: rename(&pattern(trail), names, sep),
+            data,
+        });
+    }
+
+    for child in node.nodes.iter().flatten() {
+        collect_scopes(child, sep, trail, scopes);
+    }
+
+    trail.pop();
+}
+
+/// Returns the param names of a route ending at or below the node.
+fn route_names<T>(node: &Node<T>) -> Option<&[String]> {
+    node.params.as_deref().or_else(|| {
+        node.nodes
+            .iter()
+            .flatten()
+            .find_map(|child| route_names(child))
+    })
+}
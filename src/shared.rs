@@ -0,0 +1,141 @@
+use std::{
+    ops::Deref,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use arc_swap::{ArcSwap, Guard};
+
+use crate::{Error, PathTree};
+
+/// A tree shared between threads, updated while it is searched.
+///
+/// Readers load the current tree without locking and keep it alive with a
+/// [`Snapshot`], the results of a lookup borrow from it. Writers are serialized,
+/// each update clones the current tree, changes the copy and swaps it in, so
+/// a reader sees every update at once or not at all.
+///
+/// ```
+/// use path_tree::{PathTree, SharedPathTree};
+///
+/// let mut tree = PathTree::new();
+/// tree.insert("/users/:id", 0);
+///
+/// let shared = SharedPathTree::new(tree);
+/// let snapshot = shared.load();
+///
+/// shared.insert("/users/new", 1);
+///
+/// assert_eq!(snapshot.find("/users/new").unwrap().0, &0);
+/// assert_eq!(shared.load().find("/users/new").unwrap().0, &1);
+/// ```
+#[derive(Debug)]
+pub struct SharedPathTree<T> {
+    current: ArcSwap<PathTree<T>>,
+    writer: Mutex<()>,
+}
+
+/// A version of a [`SharedPathTree`], see [`SharedPathTree::load`].
+///
+/// A snapshot is meant to live as long as a request, updates made while it is
+/// held do not free the tree it points to.
+#[derive(Debug)]
+pub struct Snapshot<T>(Guard<Arc<PathTree<T>>>);
+
+impl<T> Deref for Snapshot<T> {
+    type Target = PathTree<T>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> Default for SharedPathTree<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(PathTree::new())
+    }
+}
+
+impl<T> From<PathTree<T>> for SharedPathTree<T> {
+    #[inline]
+    fn from(tree: PathTree<T>) -> Self {
+        Self::new(tree)
+    }
+}
+
+impl<T> SharedPathTree<T> {
+    /// Creates a shared tree starting at the tree.
+    pub fn new(tree: PathTree<T>) -> Self {
+        Self {
+            current: ArcSwap::from_pointee(tree),
+            writer: Mutex::new(()),
+        }
+    }
+
+    /// Returns the current version of the tree, without locking.
+    #[inline]
+    pub fn load(&self) -> Snapshot<T> {
+        Snapshot(self.current.load())
+    }
+
+    /// Replaces the whole tree, like when a route table is reloaded, and
+    /// returns the previous version.
+    pub fn store(&self, tree: PathTree<T>) -> Arc<PathTree<T>> {
+        let _writer = self.lock();
+        self.current.swap(Arc::new(tree))
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ()> {
+        // The tree is only swapped once an update is done, a panicking writer
+        // leaves it untouched.
+        self.writer.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T> SharedPathTree<T>
+where
+    T: Clone,
+{
+    /// Changes a copy of the current tree with `f` and publishes it, the other
+    /// writers wait until it is done.
+    ///
+    /// Nothing is published if `f` panics.
+    pub fn update<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut PathTree<T>) -> R,
+    {
+        let _writer = self.lock();
+        let mut tree = PathTree::clone(&self.current.load());
+        let r = f(&mut tree);
+        self.current.store(Arc::new(tree));
+        r
+    }
+
+    /// Inserts a route, see [`PathTree::insert`].
+    pub fn insert(&self, path: &str, data: T) {
+        self.update(|tree| {
+            tree.insert(path, data);
+        });
+    }
+
+    /// Inserts a route, see [`PathTree::try_insert`]. Nothing is published if
+    /// the route is rejected.
+    pub fn try_insert(&self, path: &str, data: T) -> Result<(), Error> {
+        let _writer = self.lock();
+        let mut tree = PathTree::clone(&self.current.load());
+        tree.try_insert(path, data)?;
+        self.current.store(Arc::new(tree));
+        Ok(())
+    }
+
+    /// Removes a route and returns its data, see [`PathTree::remove`].
+    /// Nothing is published if there is no such route.
+    pub fn remove(&self, path: &str) -> Option<T> {
+        let _writer = self.lock();
+        let mut tree = PathTree::clone(&self.current.load());
+        let data = tree.remove(path)?;
+        self.current.store(Arc::new(tree));
+        Some(data)
+    }
+}
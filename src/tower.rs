@@ -0,0 +1,256 @@
+//! A [`tower_service::Service`] routing requests by their path.
+//!
+//! ```
+//! use std::convert::Infallible;
+//!
+//! use futures::executor::block_on;
+//! use http::{Method, Request, Response, StatusCode};
+//! use path_tree::{http::OwnedParams, tower::PathRouter};
+//! use tower::{service_fn, ServiceExt};
+//!
+//! async fn user(req: Request<()>) -> Result<Response<String>, Infallible> {
+//!     let params = req.extensions().get::<OwnedParams>().unwrap();
+//!     Ok(Response::new(format!("user {}", params.get("id").unwrap())))
+//! }
+//!
+//! let mut router = PathRouter::new();
+//! router.route_method(Method::GET, "/users/:id", service_fn(user));
+//!
+//! let req = Request::get("/users/42?tab=repos").body(()).unwrap();
+//! let res = block_on(router.clone().oneshot(req)).unwrap();
+//! assert_eq!(res.body(), "user 42");
+//!
+//! let req = Request::delete("/users/42").body(()).unwrap();
+//! let res = block_on(router.clone().oneshot(req)).unwrap();
+//! assert_eq!(res.status(), StatusCode::METHOD_NOT_ALLOWED);
+//! assert_eq!(res.headers()["allow"], "GET");
+//!
+//! let req = Request::get("/repos").body(()).unwrap();
+//! let res = block_on(router.oneshot(req)).unwrap();
+//! assert_eq!(res.status(), StatusCode::NOT_FOUND);
+//! ```
+
+use alloc::vec::Vec;
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{ready, Context, Poll},
+};
+
+use http::{header::ALLOW, HeaderValue, Method, Request, Response, StatusCode};
+use pin_project_lite::pin_project;
+use tower_service::Service;
+
+use crate::http::{Miss, OwnedParams, RequestTree};
+
+/// A service dispatching requests to the service of the route matching their
+/// method, host and path like [`RequestTree`] does, see the [module](self)
+/// docs. The params of the route are inserted into the extensions of the
+/// request as [`OwnedParams`].
+///
+/// A request whose path has no route goes to the fallback service, or gets
+/// an empty `404 Not Found` response. A request whose path has routes for
+/// other methods only gets an empty `405 Method Not Allowed` response with an
+/// `Allow` header.
+///
+/// The router is always ready: the matched service is cloned and the
+/// response future waits for it to be ready before calling it, like
+/// `tower::ServiceExt::oneshot` does. Cloning the router is cheap, the routes
+/// are shared.
+#[derive(Debug)]
+pub struct PathRouter<S> {
+    tree: Arc<RequestTree<S>>,
+    fallback: Option<S>,
+}
+
+impl<S> Clone for PathRouter<S>
+where
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            tree: Arc::clone(&self.tree),
+            fallback: self.fallback.clone(),
+        }
+    }
+}
+
+impl<S> Default for PathRouter<S> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> PathRouter<S> {
+    /// Creates a router without routes.
+    pub fn new() -> Self {
+        Self {
+            tree: Arc::new(RequestTree::new()),
+            fallback: None,
+        }
+    }
+}
+
+impl<S> PathRouter<S>
+where
+    S: Clone,
+{
+    /// Routes the requests of any method matching the path to the service,
+    /// unless the path has a service for their method.
+    ///
+    /// The service of an existing route with the same path is replaced, like
+    /// [`PathTree::insert`](crate::PathTree::insert) does.
+    pub fn route(&mut self, path: &str, service: S) -> &mut Self {
+        Arc::make_mut(&mut self.tree).insert(None, path, service);
+        self
+    }
+
+    /// Routes the requests of the method matching the path to the service.
+    pub fn route_method(&mut self, method: Method, path: &str, service: S) -> &mut Self {
+        Arc::make_mut(&mut self.tree).insert(Some(method), path, service);
+        self
+    }
+
+    /// Routes the requests to the host of the method, or of any method,
+    /// matching the path to the service, see [`RequestTree::insert_host`].
+    pub fn route_host(
+        &mut self,
+        host: &str,
+        method: Option<Method>,
+        path: &str,
+        service: S,
+    ) -> &mut Self {
+        Arc::make_mut(&mut self.tree).insert_host(host, method, path, service);
+        self
+    }
+
+    /// Sets the service of the requests whose path has no route.
+    pub fn fallback(&mut self, service: S) -> &mut Self {
+        self.fallback = Some(service);
+        self
+    }
+}
+
+impl<S, B, RB> Service<Request<B>> for PathRouter<S>
+where
+    S: Service<Request<B>, Response = Response<RB>> + Clone,
+    RB: Default,
+{
+    type Response = Response<RB>;
+    type Error = S::Error;
+    type Future = ResponseFuture<S, B>;
+
+    #[inline]
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, mut req: Request<B>) -> Self::Future {
+        let (service, params) = match self.tree.lookup(&req) {
+            Ok((service, params)) => (service.clone(), params),
+            Err(Miss::MethodNotAllowed(methods)) => {
+                let allow = methods
+                    .iter()
+                    .map(|m| m.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let mut res = empty(StatusCode::METHOD_NOT_ALLOWED);
+                if let Ok(allow) = HeaderValue::from_str(&allow) {
+                    res.headers_mut().insert(ALLOW, allow);
+                }
+                return ResponseFuture::respond(res);
+            }
+            Err(Miss::NotFound) => match &self.fallback {
+                Some(service) => (service.clone(), OwnedParams::default()),
+                None => return ResponseFuture::respond(empty(StatusCode::NOT_FOUND)),
+            },
+        };
+
+        req.extensions_mut().insert(params);
+
+        ResponseFuture {
+            state: State::Ready {
+                service,
+                req: Some(req),
+            },
+        }
+    }
+}
+
+fn empty<B: Default>(status: StatusCode) -> Response<B> {
+    let mut res = Response::new(B::default());
+    *res.status_mut() = status;
+    res
+}
+
+pin_project! {
+    /// The response future of [`PathRouter`].
+    pub struct ResponseFuture<S, B>
+    where
+        S: Service<Request<B>>,
+    {
+        #[pin]
+        state: State<S, B>,
+    }
+}
+
+pin_project! {
+    #[project = StateProj]
+    #[allow(clippy::large_enum_variant)]
+    enum State<S, B>
+    where
+        S: Service<Request<B>>,
+    {
+        Ready { service: S, req: Option<Request<B>> },
+        Called { #[pin] fut: S::Future },
+        Respond { res: Option<S::Response> },
+    }
+}
+
+impl<S, B> ResponseFuture<S, B>
+where
+    S: Service<Request<B>>,
+{
+    fn respond(res: S::Response) -> Self {
+        Self {
+            state: State::Respond { res: Some(res) },
+        }
+    }
+}
+
+impl<S, B> fmt::Debug for ResponseFuture<S, B>
+where
+    S: Service<Request<B>>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseFuture").finish_non_exhaustive()
+    }
+}
+
+impl<S, B> Future for ResponseFuture<S, B>
+where
+    S: Service<Request<B>>,
+{
+    type Output = Result<S::Response, S::Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        loop {
+            let mut state = self.as_mut().project().state;
+            match state.as_mut().project() {
+                StateProj::Ready { service, req } => {
+                    ready!(service.poll_ready(cx))?;
+                    let req = req.take().expect("polled after completion");
+                    let fut = service.call(req);
+                    state.set(State::Called { fut });
+                }
+                StateProj::Called { fut } => return fut.poll(cx),
+                StateProj::Respond { res } => {
+                    return Poll::Ready(Ok(res.take().expect("polled after completion")))
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use path_tree::{analyze, Diagnostic, Error, PathTree};
+
+#[allow(dead_code)]
+#[path = "fixtures/github.rs"]
+mod github;
+
+use github::ROUTES_WITH_COLON;
+
+#[test]
+fn github() {
+    assert_eq!(analyze(ROUTES_WITH_COLON), []);
+}
+
+#[test]
+fn route_set() {
+    let diagnostics = analyze([
+        "/users/:id",
+        "/users/:user_id",
+        "/users/:id/:id",
+        "/files/*path/",
+        "/files/*path/raw",
+        "/files",
+    ]);
+
+    assert_eq!(
+        diagnostics,
+        [
+            Diagnostic::Duplicate {
+                route: "/users/:user_id".to_owned(),
+                existing: "/users/:id".to_owned(),
+            },
+            Diagnostic::Invalid(Error::DuplicateParam {
+                path: "/users/:id/:id".to_owned(),
+                name: "id".to_owned(),
+            }),
+            Diagnostic::Invalid(Error::CatchAllNotLast {
+                path: "/files/*path/".to_owned(),
+            }),
+            Diagnostic::Invalid(Error::CatchAllNotLast {
+                path: "/files/*path/raw".to_owned(),
+            }),
+        ]
+    );
+}
+
+#[test]
+fn shadowing() {
+    let mut tree = PathTree::new();
+    tree.insert("/git/:org", 0)
+        .insert("/git/:org/*path", 1)
+        .insert("/git/*any", 2)
+        .insert("/git/", 6)
+        .insert("/docs/:name*ext", 3)
+        .insert("/docs/*path/", 4)
+        .insert("/docs/:name", 5);
+
+    assert_eq!(
+        tree.analyze(),
+        [
+            Diagnostic::Overlap {
+                param: "/git/:org".to_owned(),
+                catch_all: "/git/*any".to_owned(),
+            },
+            Diagnostic::Overlap {
+                param: "/git/:org/*path".to_owned(),
+                catch_all: "/git/*any".to_owned(),
+            },
+            Diagnostic::Shadowed {
+                route: "/git/*any".to_owned(),
+                by: "/git/".to_owned(),
+            },
+            Diagnostic::Overlap {
+                param: "/docs/:name".to_owned(),
+                catch_all: "/docs/*path/".to_owned(),
+            },
+            Diagnostic::Unreachable {
+                route: "/docs/:name*ext".to_owned(),
+            },
+        ]
+    );
+
+    let diagnostics = tree
+        .analyze()
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(diagnostics[2], "`/git/*any` is shadowed by `/git/`");
+}
+
+#[test]
+fn partial_overlap() {
+    let mut tree = PathTree::new();
+    tree.insert("/git/:org", 0)
+        .insert("/git/:org/*path", 1)
+        .insert("/git/*any", 2);
+
+    // `/git/` still reaches the catch-all.
+    assert_eq!(
+        tree.analyze(),
+        [
+            Diagnostic::Overlap {
+                param: "/git/:org".to_owned(),
+                catch_all: "/git/*any".to_owned(),
+            },
+            Diagnostic::Overlap {
+                param: "/git/:org/*path".to_owned(),
+                catch_all: "/git/*any".to_owned(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn trailing_slash() {
+    // Each route of a pair is returned for its paths.
+    let diagnostics = analyze([
+        "/users",
+        "/users/",
+        "/users/:id",
+        "/users/:user_id/",
+        "/files/*path",
+        "/",
+    ]);
+    assert_eq!(diagnostics, []);
+
+    let mut tree = PathTree::with_separator(".");
+    tree.insert("orders.:id", 0).insert("orders.:id.", 1);
+    assert_eq!(tree.analyze(), []);
+
+    // The params of the witness paths do not start a static sibling.
+    let mut tree = PathTree::new();
+    tree.insert("/:name", 0)
+        .insert("/:name/", 1)
+        .insert("/x/", 2)
+        .insert("/y", 3);
+    assert_eq!(tree.analyze(), []);
+    assert_eq!(tree.find("/z/"), Some((&1, vec![("name", "z")])));
+}
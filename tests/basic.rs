@@ -1,4 +1,4 @@
-use path_tree::PathTree;
+use path_tree::{PathTree, PersistentPathTree};
 use rand::seq::SliceRandom;
 
 #[test]
@@ -56,7 +56,7 @@ fn new_tree() {
     let mut routes = ROUTES
         .iter()
         .zip(VALID_URLS.iter())
-        .zip(valid_res.into_iter())
+        .zip(valid_res)
         .map(|(a, b)| (*a.0, *a.1, b))
         .collect::<Vec<_>>();
 
@@ -555,3 +555,225 @@ fn test_named_wildcard_collide() {
     assert_eq!(*res.0, 2);
     assert_eq!(res.1, [("any", "rust-lang")]);
 }
+
+#[test]
+fn custom_separator() {
+    let mut tree = PathTree::<usize>::with_separator(".");
+    tree.insert("orders", 0);
+    tree.insert("orders.:id", 1);
+    tree.insert("orders.:id.created", 2);
+    tree.insert("orders.*rest", 3);
+    tree.insert("metrics.:host.cpu", 4);
+
+    let res = tree.find(".orders").unwrap();
+    assert_eq!(*res.0, 0);
+    assert_eq!(res.1, []);
+
+    let res = tree.find(".orders.42").unwrap();
+    assert_eq!(*res.0, 1);
+    assert_eq!(res.1, [("id", "42")]);
+
+    let res = tree.find(".orders.42.created").unwrap();
+    assert_eq!(*res.0, 2);
+    assert_eq!(res.1, [("id", "42")]);
+
+    let res = tree.find(".orders.42.shipped.late").unwrap();
+    assert_eq!(*res.0, 3);
+    assert_eq!(res.1, [("rest", "42.shipped.late")]);
+
+    // `/` is an ordinary character now
+    let res = tree.find(".metrics.web/1.cpu").unwrap();
+    assert_eq!(*res.0, 4);
+    assert_eq!(res.1, [("host", "web/1")]);
+
+    assert!(tree.find(".metrics.web.1.cpu").is_none());
+    assert!(tree.find("/orders").is_none());
+}
+
+#[test]
+fn optional_leading_separator() {
+    let mut tree = PathTree::<usize>::with_separator(".");
+    tree.insert("", 0);
+    tree.insert("orders.:id", 1);
+    tree.insert("orders.*rest", 2);
+
+    for (path, found) in [
+        ("orders.42", Some((&1, vec![("id", "42")]))),
+        (".orders.42", Some((&1, vec![("id", "42")]))),
+        (
+            "orders.42.shipped",
+            Some((&2, vec![("rest", "42.shipped")])),
+        ),
+        ("", Some((&0, vec![]))),
+        (".", Some((&0, vec![]))),
+        ("/orders.42", None),
+        ("..orders.42", None),
+    ] {
+        assert_eq!(tree.find(path), found, "{}", path);
+        assert_eq!(
+            tree.find_all(path)
+                .next()
+                .map(|(data, params, _)| (data, params)),
+            found,
+            "{}",
+            path
+        );
+        assert_eq!(
+            tree.explain(path).route.is_some(),
+            found.is_some(),
+            "{}",
+            path
+        );
+        assert_eq!(tree.clone().freeze().find(path), found, "{}", path);
+        assert_eq!(PersistentPathTree::from(tree.clone()).find(path), found);
+    }
+
+    // The paths of a `/` tree keep their leading `/`.
+    let mut tree = PathTree::new();
+    tree.insert("/", 0).insert("/users/:id", 1);
+    assert_eq!(tree.find("/users/42"), Some((&1, vec![("id", "42")])));
+    for path in ["", "users/42"] {
+        assert_eq!(tree.find(path), None, "{}", path);
+        assert_eq!(tree.find_bytes(path.as_bytes()), None, "{}", path);
+        assert!(tree.find_all(path).next().is_none(), "{}", path);
+        assert!(tree.explain(path).route.is_none(), "{}", path);
+        assert_eq!(tree.clone().freeze().find(path), None, "{}", path);
+        assert_eq!(PersistentPathTree::from(tree.clone()).find(path), None);
+    }
+}
+
+#[test]
+fn many_char_separator() {
+    let mut tree = PathTree::<usize>::with_separator("::");
+    tree.insert("std", 0);
+    tree.insert("std:::module", 1);
+    tree.insert("std:::module::*item", 2);
+    tree.insert("std::vec::Vec", 3);
+    tree.insert("std:::module:::item::new", 4);
+    tree.insert("core::*", 5);
+
+    for (path, found) in [
+        ("std", Some((&0, vec![]))),
+        ("::std", Some((&0, vec![]))),
+        ("std::fmt", Some((&1, vec![("module", "fmt")]))),
+        ("std::a:b", Some((&1, vec![("module", "a:b")]))),
+        (
+            "std::collections::hash_map::HashMap",
+            Some((
+                &2,
+                vec![("module", "collections"), ("item", "hash_map::HashMap")],
+            )),
+        ),
+        ("std::vec::Vec", Some((&3, vec![]))),
+        (
+            "std::vec::Vec::new",
+            Some((&4, vec![("module", "vec"), ("item", "Vec")])),
+        ),
+        ("core::mem", Some((&5, vec![("", "mem")]))),
+        ("core::", Some((&5, vec![]))),
+        ("std:fmt", None),
+        ("alloc::vec", None),
+    ] {
+        assert_eq!(tree.find(path), found, "{}", path);
+        assert_eq!(
+            tree.find_all(path)
+                .next()
+                .map(|(data, params, _)| (data, params)),
+            found,
+            "{}",
+            path
+        );
+        assert_eq!(tree.clone().freeze().find(path), found, "{}", path);
+        assert_eq!(PersistentPathTree::from(tree.clone()).find(path), found);
+    }
+
+    assert_eq!(
+        tree.iter().map(|(pattern, _)| pattern).collect::<Vec<_>>(),
+        [
+            "::std",
+            "::std:::module",
+            "::std:::module::*item",
+            "::std:::module:::item::new",
+            "::std::vec::Vec",
+            "::core::*",
+        ]
+    );
+    assert_eq!(tree.remove("std:::name"), Some(1));
+    assert_eq!(tree.find("std::fmt"), None);
+    assert_eq!(tree.find("std::vec::Vec"), Some((&3, vec![])));
+}
+
+#[test]
+#[should_panic]
+fn colon_separator() {
+    PathTree::<usize>::with_separator(":");
+}
+
+#[test]
+#[should_panic]
+fn empty_separator() {
+    PathTree::<usize>::with_separator("");
+}
+
+#[test]
+fn backtrack_drops_params() {
+    let mut tree = PathTree::<usize>::new();
+    tree.insert("/users/:id/repos", 0);
+    tree.insert("/users/*any", 1);
+
+    let res = tree.find("/users/fundon/followers").unwrap();
+    assert_eq!(*res.0, 1);
+    assert_eq!(res.1, [("any", "fundon/followers")]);
+}
+
+#[test]
+fn backtrack_from_static_prefix() {
+    let mut tree = PathTree::<usize>::new();
+    tree.insert("/abc/x", 0);
+    tree.insert("/abd", 1);
+    tree.insert("/:name", 2);
+
+    let res = tree.find("/ab").unwrap();
+    assert_eq!(*res.0, 2);
+    assert_eq!(res.1, [("name", "ab")]);
+}
+
+#[test]
+fn remove() {
+    let mut tree = PathTree::<usize>::new();
+    tree.insert("/", 0);
+    tree.insert("/users", 1);
+    tree.insert("/users/:id", 2);
+    tree.insert("/users/:id/repos", 3);
+    tree.insert("/*any", 4);
+    tree.insert("/é", 5);
+    tree.insert("/è", 6);
+
+    assert_eq!(tree.remove("/users/:id/repo"), None);
+    assert_eq!(tree.remove("/user"), None);
+    assert_eq!(tree.remove("/users/:id/repos"), Some(3));
+    assert_eq!(tree.remove("/users/:id/repos"), None);
+    assert_eq!(tree.remove("/è"), Some(6));
+    assert_eq!(tree.remove("/*"), Some(4));
+    assert_eq!(tree.remove(""), Some(0));
+
+    assert_eq!(tree.find("/"), None);
+    assert_eq!(tree.find("/users/42/repos"), None);
+    assert_eq!(tree.find("/users/42"), Some((&2, vec![("id", "42")])));
+    assert_eq!(tree.find("/é"), Some((&5, vec![])));
+    assert_eq!(tree.find("/è"), None);
+    assert_eq!(
+        tree.iter().collect::<Vec<_>>(),
+        [
+            ("/users".to_owned(), &1),
+            ("/users/:id".to_owned(), &2),
+            ("/é".to_owned(), &5),
+        ]
+    );
+
+    assert_eq!(tree.remove("/users"), Some(1));
+    assert_eq!(tree.remove("/users/:id"), Some(2));
+    assert_eq!(tree.remove("/é"), Some(5));
+    assert_eq!(tree.iter().count(), 0);
+    assert_eq!(tree.to_string(), PathTree::<usize>::new().to_string());
+}
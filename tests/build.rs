@@ -0,0 +1,81 @@
+use path_tree::{Error, PathTree};
+
+#[allow(dead_code)]
+#[path = "fixtures/github.rs"]
+mod github;
+
+use github::*;
+
+#[test]
+fn github() {
+    let routes = ROUTES_WITH_COLON.iter().enumerate().map(|(i, r)| (*r, i));
+
+    let collected = routes.clone().collect::<PathTree<_>>();
+    let built = PathTree::build(routes.clone().rev()).unwrap();
+
+    for (i, u) in ROUTES_URLS.iter().enumerate() {
+        assert_eq!(collected.find(u).unwrap().0, &i);
+        assert_eq!(built.find(u), collected.find(u));
+    }
+    assert_eq!(built.iter().count(), ROUTES_WITH_COLON.len());
+
+    // The same tree as inserting the routes one by one.
+    let mut inserted = PathTree::new();
+    for (r, i) in routes.rev() {
+        inserted.insert(r, i);
+    }
+    assert_eq!(format!("{:?}", built), format!("{:?}", inserted));
+}
+
+#[test]
+fn extend() {
+    let mut tree = PathTree::build([("/users", 0)]).unwrap();
+    tree.extend(vec![("/users/:id".to_owned(), 1), ("/users".to_owned(), 2)]);
+
+    assert_eq!(tree.find("/users").unwrap().0, &2);
+    assert_eq!(tree.find("/users/7").unwrap().0, &1);
+}
+
+#[test]
+fn all_errors() {
+    let errors = PathTree::build([
+        ("/repos/:owner/:repo", 0),
+        ("/repos/:user/:name", 1),
+        ("/repos/:owner/:owner/x", 2),
+        ("/files/*path/raw", 3),
+        ("/users/:id", 4),
+        ("/users/:user_id", 5),
+    ])
+    .unwrap_err();
+
+    assert_eq!(
+        errors,
+        [
+            Error::Conflict {
+                path: "/repos/:user/:name".to_owned(),
+                existing: "/repos/:owner/:repo".to_owned(),
+            },
+            Error::DuplicateParam {
+                path: "/repos/:owner/:owner/x".to_owned(),
+                name: "owner".to_owned(),
+            },
+            Error::CatchAllNotLast {
+                path: "/files/*path/raw".to_owned(),
+            },
+            Error::Conflict {
+                path: "/users/:user_id".to_owned(),
+                existing: "/users/:id".to_owned(),
+            },
+        ]
+    );
+
+    // The earlier route is kept.
+    let errors = PathTree::build([("/users/:user_id", 0), ("/users/:id", 1)]).unwrap_err();
+    assert_eq!(
+        errors,
+        [Error::Conflict {
+            path: "/users/:id".to_owned(),
+            existing: "/users/:user_id".to_owned(),
+        }]
+    );
+}
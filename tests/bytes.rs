@@ -0,0 +1,106 @@
+use path_tree::PathTree;
+
+#[allow(dead_code)]
+#[path = "fixtures/github.rs"]
+mod github;
+
+use github::*;
+
+#[test]
+fn github() {
+    let tree = ROUTES_WITH_COLON
+        .iter()
+        .enumerate()
+        .map(|(i, r)| (*r, i))
+        .collect::<PathTree<_>>();
+    let frozen = tree.clone().freeze();
+
+    for path in ROUTES_URLS
+        .iter()
+        .chain(&["/", "", "/repos/", "/nope/nope"])
+    {
+        let found = tree.find(path).map(|(data, params)| {
+            let params = params
+                .into_iter()
+                .map(|(k, v)| (k, v.as_bytes()))
+                .collect::<Vec<_>>();
+            (data, params)
+        });
+
+        assert_eq!(tree.find_bytes(path.as_bytes()), found, "{}", path);
+        assert_eq!(frozen.find_bytes(path.as_bytes()), found, "{}", path);
+    }
+}
+
+#[test]
+fn not_utf8() {
+    let mut tree = PathTree::new();
+    tree.insert("/files/:name", 0)
+        .insert("/files/:name/raw", 1)
+        .insert("/*any", 2);
+
+    assert_eq!(
+        tree.find_bytes(b"/files/\xff\xfe"),
+        Some((&0, vec![("name", &b"\xff\xfe"[..])]))
+    );
+    assert_eq!(
+        tree.find_bytes(b"/files/\xc3/raw"),
+        Some((&1, vec![("name", &b"\xc3"[..])]))
+    );
+    assert_eq!(
+        tree.find_bytes(b"/\x80"),
+        Some((&2, vec![("any", &b"\x80"[..])]))
+    );
+}
+
+#[test]
+fn shared_lead_byte() {
+    // `é` and `è` both start with the byte `0xc3`.
+    let mut tree = PathTree::new();
+    tree.insert("/é", 0).insert("/è", 1).insert("/è/:id", 2);
+
+    assert_eq!(tree.find("/é"), Some((&0, vec![])));
+    assert_eq!(tree.find("/è"), Some((&1, vec![])));
+    assert_eq!(tree.find("/è/1"), Some((&2, vec![("id", "1")])));
+    assert_eq!(tree.find("/ê"), None);
+    assert_eq!(
+        tree.find_bytes("/è/1".as_bytes()),
+        Some((&2, vec![("id", &b"1"[..])]))
+    );
+    assert_eq!(tree.find_bytes(b"/\xc3"), None);
+
+    let frozen = tree.freeze();
+    assert_eq!(frozen.find("/é"), Some((&0, vec![])));
+    assert_eq!(frozen.find("/è/1"), Some((&2, vec![("id", "1")])));
+    assert_eq!(frozen.find_bytes(b"/\xc3"), None);
+}
+
+#[test]
+fn many_children() {
+    // Enough children for a dispatch table, `é` and `è` share its entry.
+    let mut tree = PathTree::new();
+    tree.insert("/a", 0)
+        .insert("/b", 1)
+        .insert("/é", 2)
+        .insert("/è", 3)
+        .insert("/è/:id", 4)
+        .insert("/:name", 5);
+
+    assert_eq!(tree.find("/a"), Some((&0, vec![])));
+    assert_eq!(tree.find("/b"), Some((&1, vec![])));
+    assert_eq!(tree.find("/é"), Some((&2, vec![])));
+    assert_eq!(tree.find("/è/1"), Some((&4, vec![("id", "1")])));
+    assert_eq!(tree.find("/ê"), Some((&5, vec![("name", "ê")])));
+    assert_eq!(tree.find("/c"), Some((&5, vec![("name", "c")])));
+    assert_eq!(
+        tree.find_bytes(b"/\xc3"),
+        Some((&5, vec![("name", &b"\xc3"[..])]))
+    );
+
+    // The table is rebuilt when a child is removed.
+    assert_eq!(tree.remove("/a"), Some(0));
+    assert_eq!(tree.remove("/é"), Some(2));
+    assert_eq!(tree.find("/a"), Some((&5, vec![("name", "a")])));
+    assert_eq!(tree.find("/b"), Some((&1, vec![])));
+    assert_eq!(tree.find("/è"), Some((&3, vec![])));
+}
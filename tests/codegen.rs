@@ -0,0 +1,83 @@
+use path_tree::{
+    codegen::{CodegenError, ErrorKind, Generator},
+    Error,
+};
+
+#[test]
+fn errors() {
+    let errors = Generator::new("Route")
+        .parse("# routes\n\nIndex /\nUser\n  Repo /a /b\nUser /users/:id id\n")
+        .unwrap_err();
+    assert_eq!(
+        errors,
+        [
+            CodegenError {
+                line: Some(4),
+                kind: ErrorKind::Parse("User".to_owned()),
+            },
+            CodegenError {
+                line: Some(5),
+                kind: ErrorKind::Parse("Repo /a /b".to_owned()),
+            },
+            CodegenError {
+                line: Some(6),
+                kind: ErrorKind::Parse("User /users/:id id".to_owned()),
+            },
+        ]
+    );
+
+    let errors = Generator::new("Route")
+        .parse(
+            "Index /\nindex /:id\nIndex /about\nfn /fn\nUser /users/:user-id\nType /types/:type\n",
+        )
+        .unwrap()
+        .route("Conflict", "/:name")
+        .generate()
+        .unwrap_err();
+    assert_eq!(
+        errors,
+        [
+            CodegenError {
+                line: Some(3),
+                kind: ErrorKind::Variant("Index".to_owned()),
+            },
+            CodegenError {
+                line: Some(4),
+                kind: ErrorKind::Variant("fn".to_owned()),
+            },
+            CodegenError {
+                line: Some(5),
+                kind: ErrorKind::Param("user-id".to_owned()),
+            },
+            CodegenError {
+                line: None,
+                kind: ErrorKind::Route(Error::Conflict {
+                    path: "/:name".to_owned(),
+                    existing: "/:id".to_owned(),
+                }),
+            },
+        ]
+    );
+    assert_eq!(
+        errors[2].to_string(),
+        "line 5: param `user-id` is not a valid field name"
+    );
+    let errors = Generator::new("Route")
+        .parse("User /users/:id name=String id=u64 id=u32\n")
+        .unwrap()
+        .generate()
+        .unwrap_err();
+    assert_eq!(
+        errors,
+        [
+            CodegenError {
+                line: Some(1),
+                kind: ErrorKind::Type("name".to_owned()),
+            },
+            CodegenError {
+                line: Some(1),
+                kind: ErrorKind::Type("id".to_owned()),
+            },
+        ]
+    );
+}
@@ -0,0 +1,93 @@
+use path_tree::PathTree;
+
+#[allow(dead_code)]
+#[path = "fixtures/github.rs"]
+mod github;
+
+use github::ROUTES_WITH_COLON;
+
+#[test]
+fn priority_order() {
+    let mut tree = PathTree::new();
+    tree.insert("/repos/*path", 0)
+        .insert("/repos/:owner/:repo", 1)
+        .insert("/repos/:owner/:repo/issues", 2)
+        .insert("/repos/new", 3)
+        .scope("/repos/:owner", |s| {
+            s.data(4);
+        })
+        .unwrap();
+
+    assert_eq!(
+        tree.to_string(),
+        "\
+/
+  repos/
+    new [/repos/new]
+    :owner (scope)
+      /
+        :repo [/repos/:owner/:repo]
+          /issues [/repos/:owner/:repo/issues]
+    *path [/repos/*path]
+"
+    );
+}
+
+#[test]
+fn param_names() {
+    let mut tree = PathTree::new();
+    tree.insert("/users/:id", 0).insert("/users/:name/repos", 1);
+
+    assert_eq!(
+        tree.to_string(),
+        "\
+/
+  users/
+    :id|:name [/users/:id]
+      /repos [/users/:name/repos]
+"
+    );
+    assert!(tree.to_dot().contains("[label=\":id|:name\\n/users/:id\""));
+}
+
+#[test]
+fn dot() {
+    let mut tree = PathTree::new();
+    tree.insert("/say/\"hi\"", 0).insert("/say/*any", 1);
+
+    assert_eq!(
+        tree.to_dot(),
+        "\
+digraph {
+    ordering=out;
+    node [shape=box];
+    n0 [label=\"/\"];
+    n1 [label=\"say/\"];
+    n2 [label=\"\\\"hi\\\"\\n/say/\\\"hi\\\"\", style=bold];
+    n1 -> n2;
+    n3 [label=\"*any\\n/say/*any\", style=bold];
+    n1 -> n3 [style=dotted];
+    n0 -> n1;
+}
+"
+    );
+}
+
+#[test]
+fn github() {
+    let mut tree = PathTree::new();
+    for (i, r) in ROUTES_WITH_COLON.iter().enumerate() {
+        tree.insert(r, i);
+    }
+
+    let text = tree.to_string();
+    let dot = tree.to_dot();
+    let nodes = text.lines().count();
+
+    assert_eq!(
+        text.lines().filter(|l| l.ends_with(']')).count(),
+        ROUTES_WITH_COLON.len()
+    );
+    assert_eq!(dot.matches(" -> ").count(), nodes - 1);
+    assert_eq!(dot.matches("style=bold").count(), ROUTES_WITH_COLON.len());
+}
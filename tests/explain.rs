@@ -0,0 +1,110 @@
+use path_tree::{NodeKind, PathTree, Reason, Step};
+
+#[test]
+fn backtracks() {
+    let mut tree = PathTree::new();
+    tree.insert("/users/:id", 0)
+        .insert("/users/:id/repos", 1)
+        .insert("/*any", 2);
+
+    let explanation = tree.explain("/users/42/stars");
+
+    assert_eq!(explanation.route.as_deref(), Some("/*any"));
+    assert_eq!(
+        explanation.steps,
+        [
+            Step::Visit {
+                depth: 0,
+                kind: NodeKind::Static("/".to_owned()),
+                path: "/users/42/stars",
+            },
+            Step::Visit {
+                depth: 1,
+                kind: NodeKind::Static("users/".to_owned()),
+                path: "users/42/stars",
+            },
+            Step::Visit {
+                depth: 2,
+                kind: NodeKind::Parameter,
+                path: "42/stars",
+            },
+            Step::Visit {
+                depth: 3,
+                kind: NodeKind::Static("/repos".to_owned()),
+                path: "/stars",
+            },
+            Step::Fail {
+                depth: 3,
+                reason: Reason::Prefix,
+            },
+            Step::Fail {
+                depth: 2,
+                reason: Reason::NoChild,
+            },
+            Step::Fail {
+                depth: 1,
+                reason: Reason::NoChild,
+            },
+            Step::Backtrack { depth: 1 },
+            Step::Visit {
+                depth: 1,
+                kind: NodeKind::CatchAll,
+                path: "users/42/stars",
+            },
+            Step::Match { depth: 1 },
+            Step::Match { depth: 0 },
+        ]
+    );
+}
+
+#[test]
+fn display() {
+    let mut tree = PathTree::new();
+    tree.insert("/users/new", 0).insert("/users/:id", 1);
+
+    assert_eq!(
+        tree.explain("/users/news").to_string(),
+        "\
+static `/` with `/users/news`
+  static `users/` with `users/news`
+    static `new` with `news`
+    failed: no child matches the rest of the path
+    backtrack
+    named parameter with `news`
+    matched
+  matched
+matched
+route `/users/:id`"
+    );
+
+    assert_eq!(
+        tree.explain("/posts").to_string(),
+        "\
+static `/` with `/posts`
+failed: no child matches the rest of the path
+no route"
+    );
+}
+
+#[test]
+fn agrees_with_find() {
+    let mut tree = PathTree::with_separator(".");
+    tree.insert("orders.:id", 0)
+        .insert("orders.:id.items", 1)
+        .insert("orders", 2);
+
+    for path in [
+        ".orders",
+        ".orders.",
+        ".orders.7",
+        ".orders.7.items",
+        ".orders.7.x",
+    ] {
+        let route = tree.explain(path).route;
+        assert_eq!(route.is_some(), tree.find(path).is_some(), "{}", path);
+    }
+    assert_eq!(
+        tree.explain(".orders.7.items").route.as_deref(),
+        Some(".orders.:id.items")
+    );
+}
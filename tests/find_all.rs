@@ -0,0 +1,138 @@
+use path_tree::PathTree;
+
+#[allow(dead_code)]
+#[path = "fixtures/github.rs"]
+mod github;
+
+use github::{ROUTES_URLS, ROUTES_WITH_COLON};
+
+#[test]
+fn priority_order() {
+    let mut tree = PathTree::<usize>::new();
+    tree.insert("/", 0);
+    tree.insert("/users", 1);
+    tree.insert("/users/:id", 2);
+    tree.insert("/users/:user_id/repos", 3);
+    tree.insert("/users/repos", 4);
+    tree.insert("/users/*any", 5);
+    tree.insert("/:username/repos", 6);
+    tree.insert("/*any", 7);
+
+    let res = tree.find_all("/users/repos").collect::<Vec<_>>();
+    assert_eq!(
+        res,
+        [
+            (&4, vec![], "/users/repos".to_owned()),
+            (&2, vec![("id", "repos")], "/users/:id".to_owned()),
+            (&5, vec![("any", "repos")], "/users/*any".to_owned()),
+            (
+                &6,
+                vec![("username", "users")],
+                "/:username/repos".to_owned()
+            ),
+            (&7, vec![("any", "users/repos")], "/*any".to_owned()),
+        ]
+    );
+
+    let res = tree.find_all("/users").collect::<Vec<_>>();
+    assert_eq!(
+        res,
+        [
+            (&1, vec![], "/users".to_owned()),
+            (&7, vec![("any", "users")], "/*any".to_owned()),
+        ]
+    );
+
+    assert_eq!(tree.find_all("/").map(|r| *r.0).collect::<Vec<_>>(), [0, 7]);
+}
+
+#[test]
+fn lazy() {
+    let mut tree = PathTree::<usize>::new();
+    tree.insert("/:a/:b/:c", 0);
+    tree.insert("/:a/*b", 1);
+    tree.insert("/*a", 2);
+
+    let mut matches = tree.find_all("/x/y/z");
+    assert_eq!(
+        matches.next(),
+        Some((
+            &0,
+            vec![("a", "x"), ("b", "y"), ("c", "z")],
+            "/:a/:b/:c".to_owned()
+        ))
+    );
+    assert_eq!(
+        matches.next(),
+        Some((&1, vec![("a", "x"), ("b", "y/z")], "/:a/*b".to_owned()))
+    );
+    assert_eq!(
+        matches.next(),
+        Some((&2, vec![("a", "x/y/z")], "/*a".to_owned()))
+    );
+    assert_eq!(matches.next(), None);
+}
+
+#[test]
+fn first_is_find() {
+    let mut tree = PathTree::<usize>::new();
+
+    for (i, r) in ROUTES_WITH_COLON.iter().enumerate() {
+        tree.insert(r, i);
+    }
+    tree.insert("/*any", ROUTES_WITH_COLON.len());
+
+    for (i, u) in ROUTES_URLS.iter().enumerate() {
+        let (data, params) = tree.find(u).unwrap();
+        let (first, first_params, pattern) = tree.find_all(u).next().unwrap();
+        assert_eq!(*data, i);
+        assert_eq!(first, data);
+        assert_eq!(first_params, params);
+        assert_eq!(pattern, ROUTES_WITH_COLON[i]);
+
+        let last = tree.find_all(u).last().unwrap();
+        assert_eq!(*last.0, ROUTES_WITH_COLON.len());
+        assert_eq!(last.1, [("any", &u[1..])]);
+    }
+}
+
+#[test]
+fn ancestors() {
+    let mut tree = PathTree::<&str>::new();
+    tree.insert("/", "root");
+    tree.insert("/*", "fallback");
+    tree.insert("/api", "api");
+    tree.insert("/api/*", "api layer");
+    tree.insert("/api/admin/*", "admin layer");
+    tree.insert("/api/admin/users/:id", "user");
+    tree.insert("/api/admin/users/:id/repos", "repos");
+    tree.insert("/api_docs", "docs");
+
+    let (data, params) = tree
+        .find_with_ancestors("/api/admin/users/42/repos")
+        .unwrap();
+    assert_eq!(
+        data,
+        [
+            &"root",
+            &"fallback",
+            &"api",
+            &"api layer",
+            &"admin layer",
+            &"user",
+            &"repos"
+        ]
+    );
+    assert_eq!(params, [("id", "42")]);
+
+    // `/api` is not a segment prefix of `/api_docs`
+    let (data, _) = tree.find_with_ancestors("/api_docs").unwrap();
+    assert_eq!(data, [&"root", &"fallback", &"docs"]);
+
+    // the matched catch-all is not repeated
+    let (data, params) = tree.find_with_ancestors("/api/other").unwrap();
+    assert_eq!(data, [&"root", &"fallback", &"api", &"api layer"]);
+    assert_eq!(params, [("", "other")]);
+
+    assert!(PathTree::<usize>::new().find_with_ancestors("/").is_none());
+}
@@ -0,0 +1,113 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+use path_tree::{ArrayParams, ParamsBuf, PathTree};
+
+#[allow(dead_code)]
+#[path = "fixtures/github.rs"]
+mod github;
+
+use github::*;
+
+/// Counts the allocations of the current thread.
+struct Counting;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|n| n.set(n.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+fn allocations<F: FnOnce()>(f: F) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+#[test]
+fn zero_allocations() {
+    let tree = ROUTES_WITH_COLON
+        .iter()
+        .enumerate()
+        .map(|(i, r)| (*r, i))
+        .collect::<PathTree<_>>();
+    let frozen = tree.clone().freeze();
+    assert!(tree.max_params() <= 8);
+
+    let mut buf = Vec::with_capacity(tree.max_params());
+
+    let n = allocations(|| {
+        for (i, u) in ROUTES_URLS.iter().enumerate() {
+            let mut params = ArrayParams::<8>::new();
+            assert_eq!(tree.find_into(u, &mut params), Some(&i));
+            assert_eq!(&*params, tree.find(u).map(|r| r.1).unwrap_or_default());
+        }
+    });
+    // `find` allocates for the expected params only.
+    assert!(n <= ROUTES_URLS.len());
+
+    let n = allocations(|| {
+        for _ in 0..100 {
+            for (i, u) in ROUTES_URLS.iter().enumerate() {
+                let mut params = ArrayParams::<8>::new();
+                assert_eq!(tree.find_into(u, &mut params), Some(&i));
+                assert_eq!(frozen.find_into(u, &mut params), Some(&i));
+                assert_eq!(tree.find_into(u, &mut buf), Some(&i));
+                assert_eq!(&buf[..], &*params);
+            }
+        }
+    });
+    assert_eq!(n, 0);
+}
+
+#[test]
+fn no_match_clears() {
+    let mut tree = PathTree::new();
+    tree.insert("/users/:id", 0).insert("/files/:dir/raw", 1);
+
+    let mut params = ArrayParams::<2>::new();
+    assert_eq!(tree.find_into("/users/42", &mut params), Some(&0));
+    assert_eq!(tree.find_into("/files/a/b", &mut params), None);
+    assert!(params.is_empty());
+
+    let frozen = tree.freeze();
+    let mut params = ArrayParams::<2>::new();
+    assert_eq!(frozen.find_into("/users/42", &mut params), Some(&0));
+    assert_eq!(&*params, [("id", "42")]);
+    assert_eq!(frozen.find_into("/files/a/b", &mut params), None);
+    assert!(params.is_empty());
+}
+
+#[test]
+fn too_small() {
+    let mut tree = PathTree::new();
+    tree.insert("/:a/:b", 0).insert("/:a", 1);
+
+    // Two params do not fit, the lookup finds no route.
+    let mut params = ArrayParams::<1>::new();
+    assert_eq!(tree.find_into("/x/y", &mut params), None);
+    assert!(params.is_empty());
+    assert!(!params.overflowed());
+
+    assert_eq!(tree.find_into("/x", &mut params), Some(&1));
+    assert_eq!(&*params, [("a", "x")]);
+
+    let frozen = tree.freeze();
+    let mut params = ArrayParams::<1>::new();
+    assert_eq!(frozen.find_into("/x/y", &mut params), None);
+    assert!(params.is_empty());
+}
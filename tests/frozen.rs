@@ -0,0 +1,131 @@
+use path_tree::PathTree;
+
+#[allow(dead_code)]
+#[path = "fixtures/github.rs"]
+mod github;
+
+use github::*;
+
+/// Checks that the frozen tree finds the same routes as the tree.
+fn same<T: Clone + std::fmt::Debug + PartialEq>(tree: &PathTree<T>, paths: &[&str]) {
+    let frozen = tree.clone().freeze();
+
+    for path in paths {
+        assert_eq!(frozen.find(path), tree.find(path), "{}", path);
+    }
+}
+
+#[test]
+fn github() {
+    let tree = ROUTES_WITH_COLON
+        .iter()
+        .enumerate()
+        .map(|(i, r)| (*r, i))
+        .collect::<PathTree<_>>();
+
+    same(&tree, &ROUTES_URLS);
+    same(&tree, &ROUTES_WITH_COLON);
+    same(&tree, &["/", "", "/repos", "/repos/", "/nope/nope"]);
+}
+
+#[test]
+fn backtracking() {
+    let mut tree = PathTree::new();
+    tree.insert("/", 0)
+        .insert("/users/:id", 1)
+        .insert("/users/:user_id/*", 2)
+        .insert("/users/:user_id/repos/:id", 3)
+        .insert("/users/new", 4)
+        .insert("/*any", 5)
+        .insert("/src/", 6)
+        .insert("/src/*filepath", 7)
+        .insert("/:name*ext", 8)
+        .insert("/files/:name", 9);
+
+    same(
+        &tree,
+        &[
+            "/",
+            "/users/42",
+            "/users/new",
+            "/users/news",
+            "/users/42/settings",
+            "/users/42/repos/7",
+            "/users/42/repos/7/branches",
+            "/src",
+            "/src/",
+            "/src/lib.rs",
+            "/files/",
+            "/files/a/b",
+            "/about",
+            "users",
+        ],
+    );
+}
+
+#[test]
+fn multi_byte() {
+    // `é` and `è` start with the same byte.
+    let mut tree = PathTree::new();
+    tree.insert("/café", 0)
+        .insert("/cafè", 1)
+        .insert("/caf/:x", 2)
+        .insert("/ü/:name", 3);
+
+    same(
+        &tree,
+        &["/café", "/cafè", "/cafe", "/caf/é", "/ü/ö", "/ü/", "/ü"],
+    );
+}
+
+#[test]
+fn dispatch() {
+    // Enough children for a dispatch table, some starting with the same byte.
+    let mut tree = PathTree::new();
+    for (i, name) in ["a", "b", "c", "é", "è", "ab", "long-segment-name", "z"]
+        .iter()
+        .enumerate()
+    {
+        tree.insert(&format!("/x/{}", name), i);
+    }
+    tree.insert("/x/:other", 8).insert("/x/*rest", 9);
+
+    same(
+        &tree,
+        &[
+            "/x/a",
+            "/x/ab",
+            "/x/abc",
+            "/x/é",
+            "/x/è",
+            "/x/ê",
+            "/x/long-segment-name",
+            "/x/long-segment",
+            "/x/z",
+            "/x/y",
+            "/x/a/b",
+        ],
+    );
+}
+
+#[test]
+fn separator() {
+    let mut tree = PathTree::with_separator(".");
+    tree.insert("orders.:id", 0)
+        .insert("orders.:id.items", 1)
+        .insert("orders.*rest", 2);
+
+    let frozen = tree.clone().freeze();
+    assert_eq!(frozen.separator(), ".");
+
+    same(
+        &tree,
+        &[
+            ".orders.7",
+            ".orders.7.items",
+            ".orders.7.x",
+            ".orders.",
+            "orders",
+        ],
+    );
+}
@@ -0,0 +1,112 @@
+use http::{header::HOST, Method, Request, Uri};
+use path_tree::{
+    http::{OwnedParams, RequestTree},
+    PathTree,
+};
+
+fn get(host: Option<&str>, uri: &str) -> Request<()> {
+    let mut req = Request::get(uri);
+    if let Some(host) = host {
+        req = req.header(HOST, host);
+    }
+    req.body(()).unwrap()
+}
+
+#[test]
+fn methods() {
+    let mut tree = RequestTree::new();
+    tree.insert(Some(Method::GET), "/users/:id", 0)
+        .insert(Some(Method::PUT), "/users/:id", 1)
+        .insert(None, "/users/:user_id/*path", 2)
+        .insert(Some(Method::GET), "/users/:user", 3);
+
+    let (data, params) = tree.find_request(&get(None, "/users/42")).unwrap();
+    assert_eq!(data, &3);
+    assert_eq!(&*params, [("user".to_owned(), "42".to_owned())]);
+
+    let req = Request::put("/users/42").body(()).unwrap();
+    assert_eq!(tree.find_request(&req).unwrap().0, &1);
+
+    let req = Request::delete("/users/42").body(()).unwrap();
+    assert_eq!(tree.find_request(&req), None);
+
+    let req = Request::delete("/users/42/a.txt").body(()).unwrap();
+    assert_eq!(tree.find_request(&req).unwrap().0, &2);
+
+    let uri = "/users/42?tab=repos".parse::<Uri>().unwrap();
+    assert_eq!(tree.find_uri(&Method::PUT, &uri).unwrap().0, &1);
+    assert_eq!(tree.find_uri(&Method::POST, &uri), None);
+}
+
+#[test]
+fn hosts() {
+    let mut tree = RequestTree::new();
+    tree.insert(None, "/users/:id", 0)
+        .insert_host("API.example.com", Some(Method::GET), "/users/:id", 1)
+        .insert_host("[::1]", None, "/users/:id", 2);
+
+    assert_eq!(tree.find_request(&get(None, "/users/42")).unwrap().0, &0);
+    assert_eq!(
+        tree.find_request(&get(Some("example.com"), "/users/42"))
+            .unwrap()
+            .0,
+        &0
+    );
+    assert_eq!(
+        tree.find_request(&get(Some("api.example.com:8080"), "/users/42"))
+            .unwrap()
+            .0,
+        &1
+    );
+    assert_eq!(
+        tree.find_request(&get(None, "https://api.EXAMPLE.com/users/42"))
+            .unwrap()
+            .0,
+        &1
+    );
+    assert_eq!(
+        tree.find_request(&get(Some("[::1]:3000"), "/users/42"))
+            .unwrap()
+            .0,
+        &2
+    );
+
+    // Routes of any host are searched after the routes of the host.
+    let req = Request::delete("/users/42")
+        .header(HOST, "api.example.com")
+        .body(())
+        .unwrap();
+    assert_eq!(tree.find_request(&req).unwrap().0, &0);
+}
+
+#[test]
+fn owned_params() {
+    let mut tree = RequestTree::new();
+    tree.insert(None, "/users/:user_id/repos/:id", "repo")
+        .insert(None, "/files/*path", "file");
+
+    // The data borrows the tree only, the params go into the request.
+    let mut req = get(
+        None,
+        "http://example.com/users/fundon/repos/path-tree?tab=code",
+    );
+    let (data, params) = tree.find_request(&req).unwrap();
+    req.extensions_mut().insert(params);
+    assert_eq!(data, &"repo");
+
+    let params = req.extensions().get::<OwnedParams>().unwrap();
+    assert_eq!(params.get("user_id"), Some("fundon"));
+    assert_eq!(params.get("id"), Some("path-tree"));
+    assert_eq!(params.get("path"), None);
+    assert_eq!(params.len(), 2);
+
+    let mut tree = PathTree::new();
+    tree.insert("/files/*path", ());
+    let params = tree
+        .find("/files/a/b.txt")
+        .unwrap()
+        .1
+        .into_iter()
+        .collect::<OwnedParams>();
+    assert_eq!(&*params, [("path".to_owned(), "a/b.txt".to_owned())]);
+}
@@ -0,0 +1,280 @@
+use path_tree::{
+    manifest::{ErrorKind, Format, ManifestError, Route},
+    Error, PathTree,
+};
+use serde::Deserialize;
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Meta {
+    auth: bool,
+}
+
+fn check(tree: PathTree<Vec<Route<Meta>>>) {
+    let (routes, params) = tree.find("/users/42").unwrap();
+    let route = &routes[0];
+    assert_eq!(route.name.as_deref(), Some("user"));
+    assert_eq!(route.methods, ["GET", "DELETE"]);
+    assert_eq!(route.metadata, Some(Meta { auth: true }));
+    assert_eq!(params, [("id", "42")]);
+
+    let (routes, params) = tree.find("/static/css/main.css").unwrap();
+    let route = &routes[0];
+    assert_eq!(route.name, None);
+    assert!(route.methods.is_empty());
+    assert_eq!(route.metadata, None);
+    assert_eq!(params, [("path", "css/main.css")]);
+}
+
+#[test]
+fn formats() {
+    check(
+        PathTree::from_manifest(
+            r#"
+[[routes]]
+pattern = "/users/:id"
+methods = ["GET", "DELETE"]
+name = "user"
+metadata = { auth = true }
+
+[[routes]]
+pattern = "/static/*path"
+"#,
+            Format::Toml,
+        )
+        .unwrap(),
+    );
+
+    check(
+        PathTree::from_manifest(
+            r#"{
+  "routes": [
+    {
+      "pattern": "/users/:id",
+      "methods": ["GET", "DELETE"],
+      "name": "user",
+      "metadata": { "auth": true }
+    },
+    { "pattern": "/static/*path" }
+  ]
+}"#,
+            Format::Json,
+        )
+        .unwrap(),
+    );
+
+    check(
+        PathTree::from_manifest(
+            "
+routes:
+  - pattern: /users/:id
+    methods: [GET, DELETE]
+    name: user
+    metadata:
+      auth: true
+  - pattern: /static/*path
+",
+            Format::Yaml,
+        )
+        .unwrap(),
+    );
+}
+
+#[test]
+fn errors() {
+    let errors = PathTree::<Vec<Route<Meta>>>::from_manifest(
+        "
+routes:
+  - pattern: /users/:id
+    name: user
+  - pattern: /users/:user_id
+    methods: [GET, 'BAD METHOD']
+  - pattern: /users/:id/:id
+    name: user
+  - pattern: /files/*path/raw
+",
+        Format::Yaml,
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        errors,
+        [
+            ManifestError {
+                line: Some(5),
+                kind: ErrorKind::Method("BAD METHOD".to_owned()),
+            },
+            ManifestError {
+                line: Some(5),
+                kind: ErrorKind::Route(Error::Conflict {
+                    path: "/users/:user_id".to_owned(),
+                    existing: "/users/:id".to_owned(),
+                }),
+            },
+            ManifestError {
+                line: Some(7),
+                kind: ErrorKind::DuplicateName("user".to_owned()),
+            },
+            ManifestError {
+                line: Some(7),
+                kind: ErrorKind::Route(Error::DuplicateParam {
+                    path: "/users/:id/:id".to_owned(),
+                    name: "id".to_owned(),
+                }),
+            },
+            ManifestError {
+                line: Some(9),
+                kind: ErrorKind::Route(Error::CatchAllNotLast {
+                    path: "/files/*path/raw".to_owned(),
+                }),
+            },
+        ]
+    );
+    assert_eq!(
+        errors[2].to_string(),
+        "line 7: the name `user` is used more than once"
+    );
+}
+
+#[test]
+fn parse_errors() {
+    let errors = PathTree::<Vec<Route<Meta>>>::from_manifest(
+        "[[routes]]\npattern = \"/\"\nmethods = 1\n",
+        Format::Toml,
+    )
+    .unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].line, Some(3));
+
+    let errors = PathTree::<Vec<Route<Meta>>>::from_manifest(
+        "{\n  \"routes\": [\n    {}\n  ]\n}",
+        Format::Json,
+    )
+    .unwrap_err();
+    assert_eq!(errors[0].line, Some(3));
+    assert!(matches!(errors[0].kind, ErrorKind::Parse(_)));
+}
+
+#[test]
+fn error_lines() {
+    // The pattern of the second route first appears in the metadata of the first.
+    let errors = PathTree::<Vec<Route<Meta>>>::from_manifest(
+        r#"
+[[routes]]
+pattern = "/users/:id"
+metadata = { auth = true, see = "/users/:user_id" }
+
+[[routes]]
+pattern = "/users/:user_id"
+"#,
+        Format::Toml,
+    )
+    .unwrap_err();
+    assert_eq!(errors[0].line, Some(7));
+
+    let errors = PathTree::<Vec<Route<Meta>>>::from_manifest(
+        r#"{
+  "routes": [
+    {
+      "metadata": { "auth": true, "see": "/users/:user_id" },
+      "pattern": "/users/:id"
+    },
+    {
+      "name": "user",
+      "pattern":
+        "/users/:user_id"
+    }
+  ]
+}"#,
+        Format::Json,
+    )
+    .unwrap_err();
+    assert_eq!(errors[0].line, Some(10));
+
+    let errors = PathTree::<Vec<Route<Meta>>>::from_manifest(
+        "
+routes:
+  - metadata: { auth: true, see: /users/:user_id }
+    pattern: /users/:id
+  - name: user
+    pattern: /users/:user_id
+",
+        Format::Yaml,
+    )
+    .unwrap_err();
+    assert_eq!(errors[0].line, Some(6));
+}
+
+#[test]
+fn methods() {
+    let tree = PathTree::<Vec<Route<Meta>>>::from_manifest(
+        "
+routes:
+  - pattern: /users/:id
+    methods: [GET]
+    name: user
+  - pattern: /users/:id
+    methods: [PUT, DELETE]
+    name: edit_user
+  - pattern: /users
+",
+        Format::Yaml,
+    )
+    .unwrap();
+
+    let (routes, _) = tree.find("/users/42").unwrap();
+    assert_eq!(routes.len(), 2);
+    let route = routes.iter().find(|r| r.serves("DELETE")).unwrap();
+    assert_eq!(route.name.as_deref(), Some("edit_user"));
+    assert!(!routes.iter().any(|r| r.serves("POST")));
+    assert!(tree.find("/users").unwrap().0[0].serves("POST"));
+
+    let errors = PathTree::<Vec<Route<Meta>>>::from_manifest(
+        "
+routes:
+  - pattern: /users/:id
+    methods: [GET, PUT]
+  - pattern: /users/:id
+    methods: [DELETE, PUT]
+  - pattern: /users/:id
+  - pattern: /users
+",
+        Format::Yaml,
+    )
+    .unwrap_err();
+
+    let conflict = ErrorKind::Route(Error::Conflict {
+        path: "/users/:id".to_owned(),
+        existing: "/users/:id".to_owned(),
+    });
+    assert_eq!(
+        errors,
+        [
+            ManifestError {
+                line: Some(5),
+                kind: conflict.clone(),
+            },
+            // Every method.
+            ManifestError {
+                line: Some(7),
+                kind: conflict,
+            },
+        ]
+    );
+}
+
+#[test]
+fn escaped_pattern_line() {
+    let errors = PathTree::<Vec<Route<Meta>>>::from_manifest(
+        r#"{
+  "routes": [
+    { "pattern": "/users/:id" },
+    {
+      "pattern": "\/users\/:user_id"
+    }
+  ]
+}"#,
+        Format::Json,
+    )
+    .unwrap_err();
+    assert_eq!(errors[0].line, Some(5));
+}
@@ -0,0 +1,187 @@
+use path_tree::{Error, PathTree};
+
+#[test]
+fn mount() {
+    let mut users = PathTree::new();
+    users
+        .insert("/", 0)
+        .insert("/users", 1)
+        .insert("/users/:id", 2)
+        .insert("/users/:user_id/repos/*any", 3);
+
+    let mut tree = PathTree::new();
+    tree.insert("/", 4)
+        .insert("/api/health", 5)
+        .insert("/*any", 6);
+    tree.mount("/api/:version/", users).unwrap();
+
+    let r = tree.find("/").unwrap();
+    assert_eq!(*r.0, 4);
+
+    let r = tree.find("/api/v1/").unwrap();
+    assert_eq!(*r.0, 0);
+    assert_eq!(r.1, [("version", "v1")]);
+
+    let r = tree.find("/api/health").unwrap();
+    assert_eq!(*r.0, 5);
+
+    let r = tree.find("/api/v1/users").unwrap();
+    assert_eq!(*r.0, 1);
+    assert_eq!(r.1, [("version", "v1")]);
+
+    let r = tree.find("/api/v2/users/fundon").unwrap();
+    assert_eq!(*r.0, 2);
+    assert_eq!(r.1, [("version", "v2"), ("id", "fundon")]);
+
+    let r = tree.find("/api/v2/users/fundon/repos/viz-rs/viz").unwrap();
+    assert_eq!(*r.0, 3);
+    assert_eq!(
+        r.1,
+        [
+            ("version", "v2"),
+            ("user_id", "fundon"),
+            ("any", "viz-rs/viz")
+        ]
+    );
+
+    let r = tree.find("/api/v2/teams").unwrap();
+    assert_eq!(*r.0, 6);
+    assert_eq!(r.1, [("any", "api/v2/teams")]);
+}
+
+#[test]
+fn merge_static_nodes() {
+    let mut a = PathTree::new();
+    a.insert("/users", 0).insert("/users/:id", 1);
+
+    let mut b = PathTree::new();
+    b.insert("/user", 2)
+        .insert("/users/new", 3)
+        .insert("/us", 4)
+        .insert("/", 5);
+
+    a.merge(b).unwrap();
+
+    for (path, i) in [
+        ("/users", 0),
+        ("/users/fundon", 1),
+        ("/user", 2),
+        ("/users/new", 3),
+        ("/us", 4),
+        ("/", 5),
+    ] {
+        assert_eq!(*a.find(path).unwrap().0, i, "{}", path);
+    }
+}
+
+#[test]
+fn nest() {
+    let mut repos = PathTree::new();
+    repos.insert("/", 0).insert("/issues/:number", 1);
+
+    let mut users = PathTree::new();
+    users.insert("/:user", 2);
+
+    let tree = PathTree::new()
+        .nest("/repos/:owner/:repo", repos)
+        .unwrap()
+        .nest("/users", users)
+        .unwrap();
+
+    let r = tree.find("/repos/viz-rs/path-tree/issues/1").unwrap();
+    assert_eq!(*r.0, 1);
+    assert_eq!(
+        r.1,
+        [("owner", "viz-rs"), ("repo", "path-tree"), ("number", "1")]
+    );
+
+    let r = tree.find("/users/fundon").unwrap();
+    assert_eq!(*r.0, 2);
+    assert_eq!(r.1, [("user", "fundon")]);
+}
+
+#[test]
+fn conflicts() {
+    let mut a = PathTree::new();
+    a.insert("/api/users/:id", 0).insert("/api/teams", 1);
+
+    let mut b = PathTree::new();
+    b.insert("/users/:user_id", 2)
+        .insert("/teams", 3)
+        .insert("/orgs", 4);
+
+    assert_eq!(
+        a.mount("/api", b).unwrap_err(),
+        [
+            Error::Conflict {
+                path: "/api/users/:user_id".to_owned(),
+                existing: "/api/users/:id".to_owned(),
+            },
+            Error::Conflict {
+                path: "/api/teams".to_owned(),
+                existing: "/api/teams".to_owned(),
+            },
+        ]
+    );
+
+    // existing routes are kept, others are mounted
+    assert_eq!(*a.find("/api/users/1").unwrap().0, 0);
+    assert_eq!(*a.find("/api/teams").unwrap().0, 1);
+    assert_eq!(*a.find("/api/orgs").unwrap().0, 4);
+
+    assert_eq!(
+        a.mount("/files/*", PathTree::new()).unwrap_err(),
+        [Error::CatchAllNotLast {
+            path: "/files/*".to_owned()
+        }]
+    );
+}
+
+#[test]
+fn try_insert() {
+    let mut tree = PathTree::new();
+    tree.try_insert("/", 0).unwrap();
+    tree.try_insert("/users/:id/*any", 1).unwrap();
+
+    assert_eq!(
+        tree.try_insert("//", 2).unwrap_err(),
+        Error::Conflict {
+            path: "//".to_owned(),
+            existing: "/".to_owned(),
+        }
+    );
+    assert_eq!(
+        tree.try_insert("users/:user_id/*", 3).unwrap_err(),
+        Error::Conflict {
+            path: "users/:user_id/*".to_owned(),
+            existing: "/users/:id/*any".to_owned(),
+        }
+    );
+
+    let r = tree.find("/users/fundon/repos").unwrap();
+    assert_eq!(*r.0, 1);
+    assert_eq!(r.1, [("id", "fundon"), ("any", "repos")]);
+}
+
+#[test]
+fn duplicate_params() {
+    let mut repos = PathTree::new();
+    repos.insert("/:id", 0).insert("/:repo/issues", 1);
+
+    let mut tree = PathTree::new();
+    assert_eq!(
+        tree.mount("/users/:id", repos).unwrap_err(),
+        [Error::DuplicateParam {
+            path: "/users/:id/:id".to_owned(),
+            name: "id".to_owned(),
+        }]
+    );
+
+    assert_eq!(
+        tree.try_insert("/users/:id/:id", 2).unwrap_err(),
+        Error::DuplicateParam {
+            path: "/users/:id/:id".to_owned(),
+            name: "id".to_owned(),
+        }
+    );
+}
@@ -0,0 +1,122 @@
+use path_tree::mqtt::{FilterError, TopicTree};
+
+fn subscribers(tree: &TopicTree<usize>, topic: &str) -> Vec<usize> {
+    let mut ids = tree.find_all(topic).map(|(_, n)| *n).collect::<Vec<_>>();
+    ids.sort_unstable();
+    ids
+}
+
+#[test]
+fn wildcards() {
+    let mut tree = TopicTree::new();
+
+    const FILTERS: [&str; 10] = [
+        "sport/tennis/player1",
+        "sport/tennis/player1/#",
+        "sport/tennis/+",
+        "sport/+",
+        "sport/#",
+        "+/+",
+        "/+",
+        "+",
+        "#",
+        "sport/+/player1",
+    ];
+
+    for (i, f) in FILTERS.iter().enumerate() {
+        tree.insert(f, i).unwrap();
+    }
+
+    assert_eq!(
+        subscribers(&tree, "sport/tennis/player1"),
+        [0, 1, 2, 4, 8, 9]
+    );
+    assert_eq!(
+        subscribers(&tree, "sport/tennis/player1/ranking"),
+        [1, 4, 8]
+    );
+    assert_eq!(subscribers(&tree, "sport"), [4, 7, 8]);
+    assert_eq!(subscribers(&tree, "sport/"), [3, 4, 5, 8]);
+    assert_eq!(subscribers(&tree, "/finance"), [5, 6, 8]);
+    assert_eq!(subscribers(&tree, "finance"), [7, 8]);
+}
+
+#[test]
+fn priority_order() {
+    let mut tree = TopicTree::new();
+    tree.insert("#", 0).unwrap();
+    tree.insert("a/+", 1).unwrap();
+    tree.insert("a/b", 2).unwrap();
+    tree.insert("a/#", 3).unwrap();
+    tree.insert("a/b", 4).unwrap();
+
+    let ids = tree.find_all("a/b").map(|(_, n)| *n).collect::<Vec<_>>();
+    assert_eq!(ids, [2, 4, 1, 3, 0]);
+
+    let filters = tree.find_all("a/c").map(|(f, _)| f).collect::<Vec<_>>();
+    assert_eq!(filters, ["a/+", "a/#", "#"]);
+}
+
+#[test]
+fn system_topics() {
+    let mut tree = TopicTree::new();
+    tree.insert("#", 0).unwrap();
+    tree.insert("+/monitor/clients", 1).unwrap();
+    tree.insert("$SYS/#", 2).unwrap();
+    tree.insert("$SYS/monitor/+", 3).unwrap();
+
+    assert_eq!(subscribers(&tree, "$SYS/monitor/clients"), [2, 3]);
+    assert_eq!(subscribers(&tree, "SYS/monitor/clients"), [0, 1]);
+}
+
+#[test]
+fn invalid_filters() {
+    let mut tree = TopicTree::new();
+
+    assert_eq!(tree.insert("", 0).unwrap_err(), FilterError::Empty);
+    assert_eq!(
+        tree.insert("sport/tennis#", 0).unwrap_err(),
+        FilterError::MisplacedWildcard
+    );
+    assert_eq!(
+        tree.insert("sport/#/ranking", 0).unwrap_err(),
+        FilterError::MisplacedWildcard
+    );
+    assert_eq!(
+        tree.insert("sport+", 0).unwrap_err(),
+        FilterError::MisplacedWildcard
+    );
+
+    tree.insert("sport/#", 0).unwrap();
+    assert_eq!(subscribers(&tree, "sport/+"), Vec::<usize>::new());
+    assert_eq!(subscribers(&tree, ""), Vec::<usize>::new());
+}
+
+#[test]
+fn reserved_chars() {
+    let mut tree = TopicTree::new();
+    tree.insert("sport/:id", 0).unwrap();
+    tree.insert("a*b/+", 1).unwrap();
+    tree.insert("100%3A/#", 2).unwrap();
+
+    assert_eq!(subscribers(&tree, "sport/:id"), [0]);
+    assert_eq!(subscribers(&tree, "sport/42"), Vec::<usize>::new());
+    assert_eq!(subscribers(&tree, "a*b/x"), [1]);
+    assert_eq!(subscribers(&tree, "ab/x"), Vec::<usize>::new());
+    assert_eq!(subscribers(&tree, "100%3A/x"), [2]);
+    assert_eq!(subscribers(&tree, "100:/x"), Vec::<usize>::new());
+}
+
+#[test]
+fn lazy_find_all() {
+    let mut tree = TopicTree::new();
+    tree.insert("sport/#", 0).unwrap();
+    tree.insert("sport/+", 1).unwrap();
+
+    let mut matches = tree.find_all("sport/tennis");
+    assert!(matches.next().is_some());
+    drop(matches);
+
+    tree.insert("sport/tennis", 2).unwrap();
+    assert_eq!(tree.find_all("sport/tennis").count(), 3);
+}
@@ -0,0 +1,263 @@
+use path_tree::{
+    openapi::{Document, OpenApiError, Operation, PathItem, ReferenceOr, Schema},
+    Error, PathTree,
+};
+
+#[allow(dead_code)]
+#[path = "fixtures/github.rs"]
+mod github;
+
+use github::*;
+
+#[test]
+fn github() {
+    let document = Document {
+        paths: ROUTES_WITH_BRACES
+            .iter()
+            .enumerate()
+            .map(|(i, r)| {
+                let item = PathItem {
+                    get: Some(Operation {
+                        operation_id: Some(i.to_string()),
+                    }),
+                    ..PathItem::default()
+                };
+                (r.to_string(), item)
+            })
+            .collect(),
+    };
+
+    let tree = PathTree::from_openapi(&document).unwrap();
+    let mut expected = PathTree::new();
+    for r in ROUTES_WITH_COLON {
+        expected.insert(&("/GET".to_owned() + r), ());
+    }
+
+    for (i, u) in ROUTES_URLS.iter().enumerate() {
+        let path = "/GET".to_owned() + u;
+        let (operation, params) = tree.find(&path).unwrap();
+
+        assert_eq!(operation.operation_id, Some(i.to_string()));
+        assert_eq!(operation.path, ROUTES_WITH_BRACES[i]);
+        assert_eq!(params, expected.find(&path).unwrap().1);
+    }
+
+    let exported = tree.to_openapi(|operation| Operation {
+        operation_id: operation.operation_id.clone(),
+    });
+    assert_eq!(exported.paths.len(), document.paths.len());
+    for (path, item) in &document.paths {
+        assert_eq!(exported.paths[path].get, item.get);
+        assert_eq!(
+            exported.paths[path]
+                .parameters
+                .iter()
+                .map(|p| format!("{{{}}}", p.as_item().unwrap().name))
+                .collect::<Vec<_>>(),
+            path.split('/')
+                .filter(|s| s.starts_with('{'))
+                .collect::<Vec<_>>()
+        );
+    }
+}
+
+#[test]
+fn export() {
+    let mut tree = PathTree::new();
+    tree.insert("/GET", 0)
+        .insert("/POST/files/:dir/*", 1)
+        .insert("/health/:check", 2);
+
+    let document = tree.to_openapi(|n| Operation {
+        operation_id: Some(n.to_string()),
+    });
+
+    assert_eq!(
+        serde_json::to_value(&document).unwrap(),
+        serde_json::json!({
+            "paths": {
+                "/": {
+                    "get": { "operationId": "0" }
+                },
+                "/files/{dir}/{param2}": {
+                    "post": { "operationId": "1" },
+                    "parameters": [
+                        {
+                            "name": "dir",
+                            "in": "path",
+                            "required": true,
+                            "schema": { "type": "string", "pattern": "^[^/]*$" }
+                        },
+                        {
+                            "name": "param2",
+                            "in": "path",
+                            "required": true,
+                            "schema": { "type": "string" }
+                        }
+                    ]
+                },
+                "/health/{check}": {
+                    "parameters": [
+                        {
+                            "name": "check",
+                            "in": "path",
+                            "required": true,
+                            "schema": { "type": "string", "pattern": "^[^/]*$" }
+                        }
+                    ]
+                }
+            }
+        })
+    );
+
+    let mut tree = PathTree::with_separator("-");
+    tree.insert("-GET-:id", ());
+    let document = tree.to_openapi(|_| Operation::default());
+    assert_eq!(
+        document.paths["-{id}"].parameters[0]
+            .as_item()
+            .unwrap()
+            .schema,
+        Some(Schema {
+            ty: Some("string".to_owned()),
+            pattern: Some("^[^\\-]*$".to_owned()),
+        })
+    );
+
+    let mut tree = PathTree::with_separator("::");
+    tree.insert("::GET:::id", ());
+    let document = tree.to_openapi(|_| Operation::default());
+    assert_eq!(
+        document.paths["::{id}"].parameters[0]
+            .as_item()
+            .unwrap()
+            .schema,
+        Some(Schema {
+            ty: Some("string".to_owned()),
+            pattern: Some("^(?:(?!::).)*$".to_owned()),
+        })
+    );
+}
+
+#[test]
+fn round_trip() {
+    // `/GET` is the method of the route, the path starts with a literal `GET`.
+    let document: Document = serde_json::from_value(serde_json::json!({
+        "paths": {
+            "/GET/items": {
+                "get": { "operationId": "getItems" }
+            },
+            "/items/GET": {
+                "post": { "operationId": "postGet" }
+            },
+            "/get": {
+                "put": { "operationId": "putGet" }
+            }
+        }
+    }))
+    .unwrap();
+
+    let tree = PathTree::from_openapi(&document).unwrap();
+    assert_eq!(tree.find("/GET/GET/items").unwrap().0.path, "/GET/items");
+
+    let exported = tree.to_openapi(|route| Operation {
+        operation_id: route.operation_id.clone(),
+    });
+    assert_eq!(exported.paths, document.paths);
+
+    // Only the methods of the operations are split off.
+    let mut tree = PathTree::new();
+    tree.insert("/FETCH/items", 0);
+    let document = tree.to_openapi(|_| Operation::default());
+    assert_eq!(document.paths["/FETCH/items"].operations().count(), 0);
+}
+
+#[test]
+fn yaml() {
+    let document: Document = serde_yaml::from_str(
+        "
+openapi: 3.0.3
+info:
+  title: Pets
+  version: 1.0.0
+paths:
+  /pets:
+    get:
+      operationId: listPets
+    post:
+      operationId: createPet
+  /pets/{petId}:
+    parameters:
+      - name: petId
+        in: path
+        required: true
+    get:
+      operationId: showPetById
+  /pets/{petId}/photos:
+    parameters:
+      - $ref: '#/components/parameters/petId'
+    get:
+      operationId: listPhotos
+components:
+  parameters:
+    petId:
+      name: petId
+      in: path
+      required: true
+",
+    )
+    .unwrap();
+
+    let tree = PathTree::from_openapi(&document).unwrap();
+
+    let (operation, _) = tree.find("/POST/pets").unwrap();
+    assert_eq!(operation.method, "POST");
+    assert_eq!(operation.operation_id.as_deref(), Some("createPet"));
+
+    let (operation, params) = tree.find("/GET/pets/7").unwrap();
+    assert_eq!(operation.operation_id.as_deref(), Some("showPetById"));
+    assert_eq!(params, [("petId", "7")]);
+
+    assert!(tree.find("/DELETE/pets/7").is_none());
+
+    assert_eq!(
+        document.paths["/pets/{petId}/photos"].parameters,
+        [ReferenceOr::Reference {
+            reference: "#/components/parameters/petId".to_owned(),
+        }]
+    );
+    let (operation, params) = tree.find("/GET/pets/7/photos").unwrap();
+    assert_eq!(operation.operation_id.as_deref(), Some("listPhotos"));
+    assert_eq!(params, [("petId", "7")]);
+}
+
+#[test]
+fn errors() {
+    let document: Document = serde_json::from_str(
+        r#"{
+            "paths": {
+                "/files/{name}.{ext}": { "get": {} },
+                "/users/{id}": { "get": {} },
+                "/users/{name}": { "get": {}, "put": {} },
+                "/a:b": { "get": {} }
+            }
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        PathTree::from_openapi(&document).unwrap_err(),
+        [
+            OpenApiError::Template {
+                path: "/a:b".to_owned(),
+            },
+            OpenApiError::Template {
+                path: "/files/{name}.{ext}".to_owned(),
+            },
+            OpenApiError::Route(Error::Conflict {
+                path: "/GET/users/:name".to_owned(),
+                existing: "/GET/users/:id".to_owned(),
+            }),
+        ]
+    );
+}
@@ -0,0 +1,124 @@
+use std::sync::Arc;
+
+use path_tree::{ArrayParams, Error, PathTree, PersistentPathTree};
+
+#[allow(dead_code)]
+#[path = "fixtures/github.rs"]
+mod github;
+
+use github::*;
+
+#[test]
+fn github() {
+    let tree = ROUTES_WITH_COLON
+        .iter()
+        .enumerate()
+        .map(|(i, r)| (*r, i))
+        .collect::<PathTree<_>>();
+    let persistent = ROUTES_WITH_COLON
+        .iter()
+        .enumerate()
+        .fold(PersistentPathTree::new(), |t, (i, r)| t.insert(r, i));
+    let converted = PersistentPathTree::from(tree.clone());
+
+    for path in ROUTES_URLS
+        .iter()
+        .chain(&["/", "", "/repos/", "/nope/nope"])
+    {
+        assert_eq!(persistent.find(path), tree.find(path), "{}", path);
+        assert_eq!(converted.find(path), tree.find(path), "{}", path);
+        assert_eq!(
+            persistent.find_bytes(path.as_bytes()),
+            tree.find_bytes(path.as_bytes()),
+            "{}",
+            path
+        );
+        assert_eq!(
+            persistent.find_all(path).collect::<Vec<_>>(),
+            tree.find_all(path).collect::<Vec<_>>(),
+            "{}",
+            path
+        );
+        assert_eq!(persistent.explain(path), tree.explain(path), "{}", path);
+    }
+}
+
+#[test]
+fn find_into() {
+    let tree = PersistentPathTree::new()
+        .insert("/users/:id", 0)
+        .insert("/users/:id/repos/:repo", 1);
+
+    let mut params = ArrayParams::<2>::new();
+    assert_eq!(tree.max_params(), 2);
+    assert_eq!(tree.find_into("/users/42/repos/7", &mut params), Some(&1));
+    assert_eq!(&*params, [("id", "42"), ("repo", "7")]);
+    assert_eq!(tree.find_into("/users", &mut params), None);
+    assert!(params.is_empty());
+}
+
+#[test]
+fn versions() {
+    // The data is not cloned.
+    #[derive(Debug, PartialEq)]
+    struct Route(usize);
+
+    let v1 = PersistentPathTree::new()
+        .insert("/users/:id", Route(0))
+        .insert("/users/:id/repos", Route(1));
+    let v2 = v1.insert("/users/new", Route(2));
+    let v3 = v2.try_insert("/*any", Route(3)).unwrap();
+
+    assert_eq!(
+        v3.try_insert("/users/:user_id", Route(4)).unwrap_err(),
+        Error::Conflict {
+            path: "/users/:user_id".to_owned(),
+            existing: "/users/:id".to_owned(),
+        }
+    );
+
+    assert_eq!(
+        v1.find("/users/new"),
+        Some((&Route(0), vec![("id", "new")]))
+    );
+    assert_eq!(v2.find("/users/new"), Some((&Route(2), vec![])));
+    assert_eq!(v2.find("/about"), None);
+    assert_eq!(v3.find("/about"), Some((&Route(3), vec![("any", "about")])));
+
+    let (v4, data) = v3.remove("/users/:id");
+    let data = data.unwrap();
+    assert_eq!(*data, Route(0));
+    assert_eq!(
+        v4.find("/users/42"),
+        Some((&Route(3), vec![("any", "users/42")]))
+    );
+    assert_eq!(v4.find("/users/42/repos").unwrap().0, &Route(1));
+    assert_eq!(v3.find("/users/42").unwrap().0, &Route(0));
+
+    // v1, v2 and v3 share the node of the route.
+    assert_eq!(Arc::strong_count(&data), 2);
+
+    let (v5, data) = v4.remove("/users/:id");
+    assert_eq!(data, None);
+    assert!(v5.ptr_eq(&v4));
+}
+
+#[test]
+fn remove_all() {
+    let mut tree = PersistentPathTree::new();
+    let routes = ["/", "/é", "/è", "/è/:id", "/users/:id/*", "/users"];
+
+    for (i, r) in routes.iter().enumerate() {
+        tree = tree.insert(r, i);
+    }
+    for (i, r) in routes.iter().enumerate() {
+        let (next, data) = tree.remove(r);
+        assert_eq!(data.as_deref(), Some(&i), "{}", r);
+        tree = next;
+
+        for (j, r) in routes.iter().enumerate() {
+            let found = tree.find(r).map(|(data, _)| *data);
+            assert_eq!(found, (j > i).then_some(j), "{}", r);
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use path_tree::{Error, PathTree};
+
+#[allow(dead_code)]
+#[path = "fixtures/github.rs"]
+mod github;
+
+use github::{ROUTES_URLS, ROUTES_WITH_COLON};
+
+#[test]
+fn github_scopes() {
+    const PREFIX: &str = "/repos/:owner/:repo";
+
+    let mut tree = PathTree::<usize>::new();
+
+    tree.scope(PREFIX, |s| {
+        for (i, r) in ROUTES_WITH_COLON.iter().enumerate() {
+            if let Some(r) = r.strip_prefix(PREFIX) {
+                s.insert(r, i);
+            }
+        }
+    })
+    .unwrap();
+
+    for (i, r) in ROUTES_WITH_COLON.iter().enumerate() {
+        if !r.starts_with(PREFIX) {
+            tree.try_insert(r, i).unwrap();
+        }
+    }
+
+    for (i, u) in ROUTES_URLS.iter().enumerate() {
+        assert_eq!(*tree.find(u).unwrap().0, i);
+    }
+}
+
+#[test]
+fn nested_scopes() {
+    let mut tree = PathTree::new();
+    tree.scope("/", |s| {
+        s.data("app");
+        s.insert("/", "index");
+        s.scope("/repos/:owner/:repo/", |s| {
+            s.data("repo");
+            s.insert("/", "repo page");
+            s.insert("/issues", "issues");
+            s.scope("pulls", |s| {
+                s.data("pulls");
+                s.insert("/:number", "pull");
+            });
+        });
+    })
+    .unwrap();
+
+    let (data, params) = tree
+        .find_with_ancestors("/repos/viz-rs/path-tree/pulls/1")
+        .unwrap();
+    // routes at a prefix come along
+    assert_eq!(
+        data,
+        [&"app", &"index", &"repo", &"repo page", &"pulls", &"pull"]
+    );
+    assert_eq!(
+        params,
+        [("owner", "viz-rs"), ("repo", "path-tree"), ("number", "1")]
+    );
+
+    let (data, _) = tree
+        .find_with_ancestors("/repos/viz-rs/path-tree/")
+        .unwrap();
+    assert_eq!(data, [&"app", &"index", &"repo", &"repo page"]);
+
+    let (data, _) = tree.find_with_ancestors("/").unwrap();
+    assert_eq!(data, [&"app", &"index"]);
+
+    // the prefix of a scope is not a route
+    assert!(tree.find("/repos/viz-rs/path-tree/pulls").is_none());
+}
+
+#[test]
+fn scope_errors() {
+    let mut tree = PathTree::new();
+    tree.insert("/users/:id", 0);
+
+    let errors = tree
+        .scope("/users/:id", |s| {
+            s.insert("/", 1);
+            s.insert("/repos/:id", 2);
+            s.insert("/repos/:repo_id", 3);
+            s.scope("/files", |s| {
+                s.insert("/*path/raw", 4);
+            });
+            s.data(5);
+            s.data(6);
+        })
+        .unwrap_err();
+
+    assert_eq!(
+        errors,
+        [
+            Error::DuplicateParam {
+                path: "/users/:id/repos/:id".to_owned(),
+                name: "id".to_owned(),
+            },
+            Error::CatchAllNotLast {
+                path: "/users/:id/files/*path/raw".to_owned(),
+            },
+            Error::Conflict {
+                path: "/users/:id".to_owned(),
+                existing: "/users/:id".to_owned(),
+            },
+        ]
+    );
+
+    let r = tree.find("/users/fundon/repos/path-tree").unwrap();
+    assert_eq!(*r.0, 3);
+    assert_eq!(r.1, [("id", "fundon"), ("repo_id", "path-tree")]);
+
+    let (data, _) = tree.find_with_ancestors("/users/fundon/").unwrap();
+    assert_eq!(data, [&5, &0, &1]);
+
+    assert_eq!(
+        tree.scope("/files/*", |_| unreachable!()).unwrap_err(),
+        [Error::CatchAllNotLast {
+            path: "/files/*".to_owned(),
+        }]
+    );
+}
@@ -0,0 +1,83 @@
+use path_tree::PathTree;
+
+#[allow(dead_code)]
+#[path = "fixtures/github.rs"]
+mod github;
+
+use github::*;
+
+#[test]
+fn round_trip() {
+    let mut tree = PathTree::new();
+    for (i, r) in ROUTES_WITH_COLON.iter().enumerate() {
+        tree.insert(r, i);
+    }
+
+    let json = serde_json::to_string(&tree).unwrap();
+    let copy: PathTree<usize> = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(serde_json::to_string(&copy).unwrap(), json);
+
+    for (i, u) in ROUTES_URLS.iter().enumerate() {
+        let r = copy.find(u).unwrap();
+        assert_eq!(*r.0, i);
+        assert_eq!(Some(r), tree.find(u));
+    }
+}
+
+#[test]
+fn scopes_and_separator() {
+    let mut tree = PathTree::with_separator(".");
+    tree.scope("orders.:id", |s| {
+        s.data(0);
+        s.insert("items.*rest", 1);
+    })
+    .unwrap();
+
+    let json = serde_json::to_value(&tree).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "separator": ".",
+            "routes": [{ "pattern": ".orders.:id.items.*rest", "data": 1 }],
+            "scopes": [{ "pattern": ".orders.:id", "data": 0 }],
+        })
+    );
+
+    let copy: PathTree<i32> = serde_json::from_value(json).unwrap();
+    let (data, params) = copy.find_with_ancestors(".orders.7.items.a.b").unwrap();
+    assert_eq!(data, [&0, &1]);
+    assert_eq!(params, [("id", "7"), ("rest", "a.b")]);
+}
+
+#[test]
+fn lenient_patterns() {
+    let mut tree = PathTree::new();
+    tree.insert("/a/:x/:x", 0).insert("/f/*p/raw", 1);
+
+    let json = serde_json::to_value(&tree).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "separator": "/",
+            "routes": [
+                { "pattern": "/a/:x/:x", "data": 0 },
+                { "pattern": "/f/*p/raw", "data": 1 },
+            ],
+        })
+    );
+
+    let copy: PathTree<i32> = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&copy).unwrap(), json);
+    assert_eq!(copy.find("/a/1/2"), tree.find("/a/1/2"));
+    assert_eq!(copy.find("/f/src/lib.rs"), tree.find("/f/src/lib.rs"));
+}
+
+#[test]
+fn invalid() {
+    let err =
+        serde_json::from_str::<PathTree<i32>>(r#"{"separator":":","routes":[]}"#).unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("`:` can not be used as a separator"));
+}
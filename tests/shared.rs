@@ -0,0 +1,93 @@
+use std::{sync::Arc, thread};
+
+use path_tree::{Error, PathTree, SharedPathTree};
+
+#[test]
+fn snapshots() {
+    let shared = SharedPathTree::default();
+    shared.insert("/users/:id", 0);
+
+    let before = shared.load();
+    shared.insert("/users/new", 1);
+    assert_eq!(shared.remove("/users/:id"), Some(0));
+    assert_eq!(shared.remove("/users/:id"), None);
+
+    assert_eq!(before.find("/users/new").unwrap().0, &0);
+    assert_eq!(before.find("/users/42").unwrap().0, &0);
+
+    let after = shared.load();
+    assert_eq!(after.find("/users/new").unwrap().0, &1);
+    assert_eq!(after.find("/users/42"), None);
+
+    let mut tree = PathTree::new();
+    tree.insert("/", 2);
+    let previous = shared.store(tree);
+    assert_eq!(previous.find("/users/new").unwrap().0, &1);
+    assert_eq!(shared.load().find("/").unwrap().0, &2);
+}
+
+#[test]
+fn rejected_updates() {
+    let shared = SharedPathTree::from(PathTree::new());
+    shared.try_insert("/:a", 0).unwrap();
+
+    let before = shared.load();
+    assert_eq!(
+        shared.try_insert("/:b", 1),
+        Err(Error::Conflict {
+            path: "/:b".to_owned(),
+            existing: "/:a".to_owned(),
+        })
+    );
+
+    let result = thread::scope(|s| {
+        s.spawn(|| {
+            shared.update(|tree| {
+                tree.insert("/x", 2);
+                panic!("failed update");
+            })
+        })
+        .join()
+    });
+    assert!(result.is_err());
+
+    // The writer lock is poisoned but the tree is unchanged.
+    let after = shared.load();
+    assert!(std::ptr::eq(&*before, &*after));
+    shared.insert("/y", 3);
+    assert_eq!(shared.load().find("/y").unwrap().0, &3);
+}
+
+#[test]
+fn concurrent() {
+    let shared = Arc::new(SharedPathTree::default());
+    shared.insert("/static", usize::MAX);
+
+    let readers = (0..4)
+        .map(|_| {
+            let shared = shared.clone();
+            thread::spawn(move || {
+                for _ in 0..1000 {
+                    let snapshot = shared.load();
+                    assert_eq!(snapshot.find("/static").unwrap().0, &usize::MAX);
+
+                    // A route and its sibling are published together.
+                    if let Some((i, _)) = snapshot.find("/a/x") {
+                        assert_eq!(snapshot.find("/b/x").unwrap().0, i);
+                    }
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for i in 0..100 {
+        shared.update(|tree| {
+            tree.insert("/a/:id", i).insert("/b/:id", i);
+        });
+    }
+
+    for reader in readers {
+        reader.join().unwrap();
+    }
+    assert_eq!(shared.load().find("/a/x").unwrap().0, &99);
+}
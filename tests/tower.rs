@@ -0,0 +1,148 @@
+use std::{
+    convert::Infallible,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+
+use futures::{executor::block_on, future::BoxFuture, FutureExt};
+use http::{Method, Request, Response, StatusCode};
+use path_tree::{http::OwnedParams, tower::PathRouter};
+use tower::{service_fn, util::BoxCloneService, Service, ServiceExt};
+
+type Svc = BoxCloneService<Request<()>, Response<String>, Infallible>;
+
+fn reply(name: &'static str) -> Svc {
+    BoxCloneService::new(service_fn(move |req: Request<()>| async move {
+        let params = req.extensions().get::<OwnedParams>().unwrap();
+        let params = params
+            .iter()
+            .map(|(k, v)| format!("{k}={v}"))
+            .collect::<Vec<_>>();
+        Ok(Response::new(format!("{name} {}", params.join(","))))
+    }))
+}
+
+fn call(router: &PathRouter<Svc>, method: Method, uri: &str) -> Response<String> {
+    let req = Request::builder().method(method).uri(uri).body(()).unwrap();
+    block_on(router.clone().oneshot(req)).unwrap()
+}
+
+#[test]
+fn dispatch() {
+    let mut router = PathRouter::new();
+    router
+        .route("/", reply("index"))
+        .route_method(Method::GET, "/users/:id", reply("get user"))
+        .route_method(Method::DELETE, "/users/:id", reply("delete user"))
+        .route("/users/:user_id/*path", reply("files"))
+        .route_method(Method::POST, "/users/:user_id/*path", reply("upload"));
+
+    assert_eq!(call(&router, Method::GET, "/").body(), "index ");
+    assert_eq!(call(&router, Method::PUT, "/").body(), "index ");
+    assert_eq!(
+        call(&router, Method::GET, "/users/42?tab=repos").body(),
+        "get user id=42"
+    );
+    assert_eq!(
+        call(&router, Method::DELETE, "/users/42").body(),
+        "delete user id=42"
+    );
+    assert_eq!(
+        call(&router, Method::GET, "/users/42/a/b.txt").body(),
+        "files user_id=42,path=a/b.txt"
+    );
+    assert_eq!(
+        call(&router, Method::POST, "/users/42/a/b.txt").body(),
+        "upload user_id=42,path=a/b.txt"
+    );
+
+    router.route_host("api.example.com", None, "/users/:id", reply("api user"));
+    assert_eq!(
+        call(&router, Method::PATCH, "http://api.example.com/users/42").body(),
+        "api user id=42"
+    );
+    assert_eq!(
+        call(&router, Method::DELETE, "http://example.com/users/42").body(),
+        "delete user id=42"
+    );
+
+    // The service of a route is replaced.
+    router.route_method(Method::GET, "/users/:user", reply("user"));
+    assert_eq!(
+        call(&router, Method::GET, "/users/42").body(),
+        "user user=42"
+    );
+}
+
+#[test]
+fn not_found() {
+    let mut router = PathRouter::new();
+    router
+        .route_method(Method::GET, "/users/:id", reply("get user"))
+        .route_method(Method::PUT, "/users/:id", reply("put user"));
+
+    let res = call(&router, Method::GET, "/repos");
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    assert_eq!(res.body(), "");
+
+    let res = call(&router, Method::POST, "/users/42");
+    assert_eq!(res.status(), StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(res.headers()["allow"], "GET, PUT");
+    assert_eq!(res.body(), "");
+
+    router.fallback(reply("fallback"));
+    let res = call(&router, Method::GET, "/repos");
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.body(), "fallback ");
+
+    let res = call(&router, Method::POST, "/users/42");
+    assert_eq!(res.status(), StatusCode::METHOD_NOT_ALLOWED);
+}
+
+#[derive(Clone)]
+struct Limited {
+    ready: Arc<AtomicUsize>,
+    calls: Arc<AtomicUsize>,
+}
+
+impl Service<Request<()>> for Limited {
+    type Response = Response<String>;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        if self.ready.fetch_add(1, Ordering::SeqCst) == 0 {
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        } else {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    fn call(&mut self, _: Request<()>) -> Self::Future {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        async { Ok(Response::new("limited".to_owned())) }.boxed()
+    }
+}
+
+#[test]
+fn poll_ready() {
+    let service = Limited {
+        ready: Arc::default(),
+        calls: Arc::default(),
+    };
+    let mut router = PathRouter::new();
+    router.route("/", service.clone());
+
+    let req = Request::get("/").body(()).unwrap();
+    let fut = router.call(req);
+    assert_eq!(service.calls.load(Ordering::SeqCst), 0);
+
+    let res = block_on(fut).unwrap();
+    assert_eq!(res.body(), "limited");
+    assert_eq!(service.ready.load(Ordering::SeqCst), 2);
+    assert_eq!(service.calls.load(Ordering::SeqCst), 1);
+}